@@ -1,7 +1,8 @@
 use std::path::PathBuf;
 use veusz::data::Data;
 use veusz::export::SvgExport;
-use veusz::page::{Axis, Graph, Page, Xy};
+use veusz::page::key::Key;
+use veusz::page::{Alignment, Axis, Graph, Page, Xy};
 use veusz::style::line::LineStyle;
 use veusz::style::marker::Marker;
 use veusz::style::plot::PlotLine;
@@ -11,7 +12,7 @@ use veusz::Veusz;
 const PI2: u32 = 629;
 const SCALE: f64 = 100.0;
 
-fn main() {
+fn main() -> veusz::Result<()> {
     Veusz::default()
         .with_page(
             Page::default().with_item(
@@ -21,6 +22,7 @@ fn main() {
                     .with_xy(
                         Xy::data("x-data", "y-sin")
                             .with_marker(Marker::None)
+                            .with_key_text("sin(x)")
                             .with_plot_line(
                                 PlotLine::default()
                                     .with_color_by_name(ColorName::Blue)
@@ -31,12 +33,18 @@ fn main() {
                     .with_xy(
                         Xy::data("x-data", "y-cos")
                             .with_marker(Marker::None)
+                            .with_key_text("cos(x)")
                             .with_plot_line(
                                 PlotLine::default()
                                     .with_color_by_name(ColorName::Red)
                                     .with_style(LineStyle::Solid)
                                     .with_width(3.0),
                             ),
+                    )
+                    .with_item(
+                        Key::default()
+                            .with_alignment_horizontal(Alignment::Left)
+                            .with_alignment_vertical(Alignment::Bottom),
                     ),
             ),
         )
@@ -58,5 +66,6 @@ fn main() {
             )
             .with_color(true),
         )
-        .open();
+        .open()?;
+    Ok(())
 }
@@ -1,4 +1,4 @@
-use crate::api1::ToParentDropGuard;
+use crate::api1::{PyStr, ToParentDropGuard};
 use std::fmt::Display;
 use std::io::Write;
 
@@ -9,8 +9,9 @@ impl Add<'_, '_> {
     pub fn write<W: Write>(self, writer: &mut W) -> std::io::Result<()> {
         writeln!(
             writer,
-            "Add('{}', name=u'{}', autoadd=False)",
-            self.0, self.1
+            "Add({}, name={}, autoadd=False)",
+            PyStr(self.0),
+            PyStr(self.1)
         )
     }
 }
@@ -24,7 +25,7 @@ impl ToUnique<'_> {
         writer: &mut W,
         f: F,
     ) -> std::io::Result<()> {
-        writeln!(writer, "To(u'{}')", self.0)?;
+        writeln!(writer, "To({})", PyStr(self.0))?;
         ToParentDropGuard::on(writer, f)
     }
 }
@@ -34,7 +35,7 @@ pub struct Set<'a, 'b>(pub &'a str, pub &'b str);
 
 impl Set<'_, '_> {
     pub fn write<W: Write>(self, writer: &mut W) -> std::io::Result<()> {
-        writeln!(writer, "Set('{}', u'{}')", self.0, self.1)
+        writeln!(writer, "Set({}, {})", PyStr(self.0), PyStr(self.1))
     }
 }
 
@@ -43,7 +44,7 @@ pub struct SetRaw<'a, V: Display>(pub &'a str, pub V);
 
 impl<V: Display> SetRaw<'_, V> {
     pub fn write<W: Write>(self, writer: &mut W) -> std::io::Result<()> {
-        writeln!(writer, "Set('{}', {})", self.0, self.1)
+        writeln!(writer, "Set({}, {})", PyStr(self.0), self.1)
     }
 }
 
@@ -52,7 +53,77 @@ pub struct SetData<'a, 'b>(pub &'a str, pub &'b str);
 
 impl SetData<'_, '_> {
     pub fn write<W: Write>(self, writer: &mut W) -> std::io::Result<()> {
-        writeln!(writer, "SetData(u'{}', [{}])", self.0, self.1)
+        writeln!(writer, "SetData({}, {})", PyStr(self.0), self.1)
+    }
+}
+
+/// `SetData` including the optional symmetric, negative and positive error columns.
+#[must_use]
+pub struct SetDataWithErrors<'a> {
+    pub name: &'a str,
+    pub values: &'a str,
+    pub symerr: Option<&'a str>,
+    pub negerr: Option<&'a str>,
+    pub poserr: Option<&'a str>,
+}
+
+impl SetDataWithErrors<'_> {
+    pub fn write<W: Write>(self, writer: &mut W) -> std::io::Result<()> {
+        write!(writer, "SetData({}, {}", PyStr(self.name), self.values)?;
+        for (name, errors) in [
+            ("symerr", self.symerr),
+            ("negerr", self.negerr),
+            ("poserr", self.poserr),
+        ] {
+            if let Some(errors) = errors {
+                write!(writer, ", {name}={errors}")?;
+            }
+        }
+        writeln!(writer, ")")
+    }
+}
+
+/// Name, rows and further keyword parameters of a `SetData2D` call.
+#[must_use]
+pub struct SetData2D<'a, 'b, 'c>(pub &'a str, pub &'b str, pub &'c [String]);
+
+impl SetData2D<'_, '_, '_> {
+    pub fn write<W: Write>(self, writer: &mut W) -> std::io::Result<()> {
+        write!(writer, "SetData2D({}, {}", PyStr(self.0), self.1)?;
+        for parameter in self.2 {
+            write!(writer, ", {parameter}")?;
+        }
+        writeln!(writer, ")")
+    }
+}
+
+/// Name and the python list of unicode strings of a `SetDataText` call.
+#[must_use]
+pub struct SetDataText<'a, 'b>(pub &'a str, pub &'b str);
+
+impl SetDataText<'_, '_> {
+    pub fn write<W: Write>(self, writer: &mut W) -> std::io::Result<()> {
+        writeln!(writer, "SetDataText({}, {})", PyStr(self.0), self.1)
+    }
+}
+
+/// Descriptor and the lines to import, like `x(date)` and ISO date-times.
+#[must_use]
+pub struct ImportString<'a, 'b>(pub &'a str, pub &'b str);
+
+impl ImportString<'_, '_> {
+    pub fn write<W: Write>(self, writer: &mut W) -> std::io::Result<()> {
+        writeln!(writer, "ImportString({}, {})", PyStr(self.0), PyStr(self.1))
+    }
+}
+
+/// A command with the given, already formatted positional and keyword arguments.
+#[must_use]
+pub struct Call<'a, 'b>(pub &'a str, pub &'b [String]);
+
+impl Call<'_, '_> {
+    pub fn write<W: Write>(self, writer: &mut W) -> std::io::Result<()> {
+        writeln!(writer, "{}({})", self.0, self.1.join(", "))
     }
 }
 
@@ -61,6 +132,56 @@ pub struct Load<'a>(pub &'a str);
 
 impl Load<'_> {
     pub fn write<W: Write>(self, writer: &mut W) -> std::io::Result<()> {
-        writeln!(writer, "Load('{}')", self.0)
+        writeln!(writer, "Load({})", PyStr(self.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api1::written_by;
+
+    #[test]
+    fn add_escapes_name() {
+        assert_eq!(
+            "Add(u'label', name=u'O\\'Brien', autoadd=False)\n",
+            written_by(|w| Add("label", "O'Brien").write(w))
+        );
+    }
+
+    #[test]
+    fn to_unique_escapes_name() {
+        assert_eq!(
+            "To(u'a\\\\b')\nTo('..')\n",
+            written_by(|w| ToUnique("a\\b").for_call(w, |_| Ok(())))
+        );
+    }
+
+    #[test]
+    fn set_escapes_path_and_value() {
+        assert_eq!(
+            "Set(u'label', u'line\\nbreak \\'quoted\\'')\n",
+            written_by(|w| Set("label", "line\nbreak 'quoted'").write(w))
+        );
+        assert_eq!(
+            "Set(u'min', 1.5)\n",
+            written_by(|w| SetRaw("min", 1.5).write(w))
+        );
+    }
+
+    #[test]
+    fn set_data_escapes_name() {
+        assert_eq!(
+            "SetData(u'\\xb5s\\'', [1, 2])\n",
+            written_by(|w| SetData("µs'", "[1, 2]").write(w))
+        );
+    }
+
+    #[test]
+    fn load_escapes_path() {
+        assert_eq!(
+            "Load(u'C:\\\\plots\\\\it\\'s.vsz')\n",
+            written_by(|w| Load("C:\\plots\\it's.vsz").write(w))
+        );
     }
 }
@@ -1,4 +1,4 @@
-use std::fmt::{Debug, Display, Formatter};
+use std::fmt::{Debug, Display, Formatter, Write as _};
 use std::io::Result;
 use std::io::Write;
 use std::marker::PhantomData;
@@ -53,6 +53,122 @@ impl<T: CommandLineEmbeddingInterface> AutoNameIncrement<T> {
     }
 }
 
+/// Formats the wrapped text as a python unicode string literal (`u'...'`), escaping
+/// quotes, backslashes, control and non-ASCII characters so that arbitrary text can
+/// safely be embedded into a command.
+pub(crate) struct PyStr<'a>(pub &'a str);
+
+impl Display for PyStr<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("u'")?;
+        for c in self.0.chars() {
+            match c {
+                '\\' => f.write_str("\\\\")?,
+                '\'' => f.write_str("\\'")?,
+                '\n' => f.write_str("\\n")?,
+                '\r' => f.write_str("\\r")?,
+                '\t' => f.write_str("\\t")?,
+                ' '..='~' => f.write_char(c)?,
+                c if (c as u32) <= 0xFF => write!(f, "\\x{:02x}", c as u32)?,
+                c if (c as u32) <= 0xFFFF => write!(f, "\\u{:04x}", c as u32)?,
+                c => write!(f, "\\U{:08x}", c as u32)?,
+            }
+        }
+        f.write_char('\'')
+    }
+}
+
+/// Formats the wrapped items as a python list, like [`PyNumber`]s or already formatted
+/// python expressions.
+#[derive(Copy, Clone)]
+pub(crate) struct PyList<I>(pub I);
+
+impl<I> Display for PyList<I>
+where
+    I: IntoIterator + Clone,
+    I::Item: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_char('[')?;
+        for (index, item) in self.0.clone().into_iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+            item.fmt(f)?;
+        }
+        f.write_char(']')
+    }
+}
+
+/// Formats the wrapped texts as a python list of unicode string literals.
+pub(crate) struct PyStrList<'a, S: AsRef<str>>(pub &'a [S]);
+
+impl<S: AsRef<str>> Display for PyStrList<'_, S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        PyList(self.0.iter().map(|value| PyStr(value.as_ref()))).fmt(f)
+    }
+}
+
+/// Numbers which can be written as python literal, like the values of a
+/// [`Data`](crate::data::Data) set.
+pub trait ToPyNumber {
+    fn fmt_py_number(&self, f: &mut Formatter<'_>) -> std::fmt::Result;
+}
+
+macro_rules! integer_to_py_number {
+    ($($integer:ty),*) => {
+        $(
+            impl ToPyNumber for $integer {
+                fn fmt_py_number(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                    Display::fmt(self, f)
+                }
+            }
+        )*
+    };
+}
+
+integer_to_py_number!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+macro_rules! float_to_py_number {
+    ($($float:ty),*) => {
+        $(
+            /// Rust formats non-finite floats as `NaN`, `inf` and `-inf`, which python
+            /// does not parse.
+            impl ToPyNumber for $float {
+                fn fmt_py_number(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                    if self.is_nan() {
+                        f.write_str("float('nan')")
+                    } else if self.is_infinite() && self.is_sign_positive() {
+                        f.write_str("float('inf')")
+                    } else if self.is_infinite() {
+                        f.write_str("float('-inf')")
+                    } else {
+                        Display::fmt(self, f)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+float_to_py_number!(f32, f64);
+
+impl<T: ToPyNumber + ?Sized> ToPyNumber for &T {
+    fn fmt_py_number(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        (**self).fmt_py_number(f)
+    }
+}
+
+/// Formats the wrapped number as python literal.
+#[derive(Copy, Clone)]
+pub(crate) struct PyNumber<T: ToPyNumber>(pub T);
+
+impl<T: ToPyNumber> Display for PyNumber<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt_py_number(f)
+    }
+}
+
 pub(crate) trait AsVueszApi1ValueStr {
     fn as_veusz_api1_value_str(&self) -> &str;
 }
@@ -65,3 +181,91 @@ impl AsVueszApi1ValueStr for bool {
         }
     }
 }
+
+/// Writes the item for a test and replaces its quoted, usually generated, `name` by
+/// `NAME`. Items without such a name pass an empty one.
+#[cfg(test)]
+pub(crate) fn written(item: &impl CommandLineEmbeddingInterface, name: &str) -> String {
+    let written = written_by(|writer| item.write(writer));
+    match name {
+        "" => written,
+        name => written.replace(&PyStr(name).to_string(), "NAME"),
+    }
+}
+
+/// Collects the output of `write`, for testing the commands in [`cmd`] directly.
+#[cfg(test)]
+pub(crate) fn written_by(write: impl FnOnce(&mut Vec<u8>) -> Result<()>) -> String {
+    let mut buffer = Vec::new();
+    write(&mut buffer).unwrap();
+    String::from_utf8(buffer).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn py_str_plain() {
+        assert_eq!("u'x-data'", PyStr("x-data").to_string());
+        assert_eq!("u''", PyStr("").to_string());
+    }
+
+    #[test]
+    fn py_str_quotes_and_backslashes() {
+        assert_eq!(r"u'O\'Brien'", PyStr("O'Brien").to_string());
+        assert_eq!(
+            r"u'C:\\data\\file.csv'",
+            PyStr(r"C:\data\file.csv").to_string()
+        );
+        assert_eq!(r#"u'say "hi"'"#, PyStr(r#"say "hi""#).to_string());
+        assert_eq!(r"u'\')\nImport(\''", PyStr("')\nImport('").to_string());
+    }
+
+    #[test]
+    fn py_str_control_characters() {
+        assert_eq!(r"u'a\nb\rc\td'", PyStr("a\nb\rc\td").to_string());
+        assert_eq!(r"u'\x00\x1b\x7f'", PyStr("\0\u{1b}\u{7f}").to_string());
+    }
+
+    #[test]
+    fn py_str_non_ascii() {
+        assert_eq!(r"u'\xb5m'", PyStr("µm").to_string());
+        assert_eq!(r"u'\u03c7\xb2'", PyStr("χ²").to_string());
+        assert_eq!(r"u'\U0001f600'", PyStr("😀").to_string());
+    }
+
+    #[test]
+    fn py_number() {
+        assert_eq!("1", PyNumber(1.0).to_string());
+        assert_eq!("-0.5", PyNumber(-0.5f32).to_string());
+        assert_eq!("18446744073709551615", PyNumber(u64::MAX).to_string());
+        assert_eq!("float('nan')", PyNumber(f64::NAN).to_string());
+        assert_eq!("float('inf')", PyNumber(f32::INFINITY).to_string());
+        assert_eq!("float('-inf')", PyNumber(&f64::NEG_INFINITY).to_string());
+    }
+
+    #[test]
+    fn py_list() {
+        assert_eq!("[]", PyList(&[] as &[u8]).to_string());
+        assert_eq!(
+            "[1, 2.5]",
+            PyList([PyNumber(1.0), PyNumber(2.5)]).to_string()
+        );
+        assert_eq!(
+            "[[1], [float('nan')]]",
+            PyList(
+                [[1.0], [f64::NAN]]
+                    .iter()
+                    .map(|row| PyList(row.map(PyNumber)))
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn py_str_list() {
+        assert_eq!("[]", PyStrList::<&str>(&[]).to_string());
+        assert_eq!(r"[u'a', u'b\'c']", PyStrList(&["a", "b'c"]).to_string());
+    }
+}
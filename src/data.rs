@@ -1,27 +1,921 @@
 use crate::api1::cmd;
-use crate::CommandLineEmbeddingInterface;
-use std::fmt::Display;
+use crate::api1::{AsVueszApi1ValueStr, PyList, PyNumber, PyStr, PyStrList, ToPyNumber};
+use crate::{CommandLineEmbeddingInterface, Error, Result};
+use std::fmt::{Display, Formatter};
 use std::io::Write;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(derive_more::From)]
+pub enum Dataset {
+    Data(Data),
+    Data2D(Data2D),
+    Text(DataText),
+    DateTime(DataDateTime),
+    Expression(DataExpression),
+    Range(DataRange),
+    Expression2D(Data2DExpression),
+    XYFunc2D(Data2DXYFunc),
+}
+
+impl CommandLineEmbeddingInterface for Dataset {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        match self {
+            Dataset::Data(data) => data.write(writer),
+            Dataset::Data2D(data) => data.write(writer),
+            Dataset::Text(data) => data.write(writer),
+            Dataset::DateTime(data) => data.write(writer),
+            Dataset::Expression(data) => data.write(writer),
+            Dataset::Range(data) => data.write(writer),
+            Dataset::Expression2D(data) => data.write(writer),
+            Dataset::XYFunc2D(data) => data.write(writer),
+        }
+    }
+}
 
 pub struct Data {
     name: String,
-    value: String,
+    values: Column,
+    symerr: Option<Column>,
+    negerr: Option<Column>,
+    poserr: Option<Column>,
 }
 
 impl Data {
-    pub fn new<D: Display>(name: impl Into<String>, data: impl Iterator<Item = D>) -> Self {
+    pub fn new(name: impl Into<String>, data: impl IntoIterator<Item = impl ToPyNumber>) -> Self {
         Data {
             name: name.into(),
-            value: data
-                .map(|d| format!("{d}"))
-                .collect::<Vec<String>>()
-                .join(", "),
+            values: Column::new(data),
+            symerr: None,
+            negerr: None,
+            poserr: None,
         }
     }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.len() == 0
+    }
+
+    /// Symmetric errors, one for each value.
+    pub fn set_symerr(&mut self, errors: impl IntoIterator<Item = impl ToPyNumber>) -> Result<()> {
+        self.symerr = Some(self.error_column("symerr", errors)?);
+        Ok(())
+    }
+
+    pub fn with_symerr(
+        mut self,
+        errors: impl IntoIterator<Item = impl ToPyNumber>,
+    ) -> Result<Self> {
+        self.set_symerr(errors)?;
+        Ok(self)
+    }
+
+    /// Negative errors, one for each value.
+    pub fn set_negerr(&mut self, errors: impl IntoIterator<Item = impl ToPyNumber>) -> Result<()> {
+        self.negerr = Some(self.error_column("negerr", errors)?);
+        Ok(())
+    }
+
+    pub fn with_negerr(
+        mut self,
+        errors: impl IntoIterator<Item = impl ToPyNumber>,
+    ) -> Result<Self> {
+        self.set_negerr(errors)?;
+        Ok(self)
+    }
+
+    /// Positive errors, one for each value.
+    pub fn set_poserr(&mut self, errors: impl IntoIterator<Item = impl ToPyNumber>) -> Result<()> {
+        self.poserr = Some(self.error_column("poserr", errors)?);
+        Ok(())
+    }
+
+    pub fn with_poserr(
+        mut self,
+        errors: impl IntoIterator<Item = impl ToPyNumber>,
+    ) -> Result<Self> {
+        self.set_poserr(errors)?;
+        Ok(self)
+    }
+
+    fn error_column(
+        &self,
+        kind: &str,
+        errors: impl IntoIterator<Item = impl ToPyNumber>,
+    ) -> Result<Column> {
+        let column = Column::new(errors);
+        if column.len() != self.len() {
+            return Err(Error::Invalid(format!(
+                "dataset '{}' has {} values but {} {kind} values",
+                self.name,
+                self.len(),
+                column.len()
+            )));
+        }
+        Ok(column)
+    }
 }
 
 impl CommandLineEmbeddingInterface for Data {
     fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        cmd::SetData(&self.name, &self.value).write(writer)
+        let symerr = self.symerr.as_ref().map(ToString::to_string);
+        let negerr = self.negerr.as_ref().map(ToString::to_string);
+        let poserr = self.poserr.as_ref().map(ToString::to_string);
+        cmd::SetDataWithErrors {
+            name: &self.name,
+            values: &self.values.to_string(),
+            symerr: symerr.as_deref(),
+            negerr: negerr.as_deref(),
+            poserr: poserr.as_deref(),
+        }
+        .write(writer)
+    }
+}
+
+/// A two dimensional dataset for images and contours, stored row by row. The first row
+/// is at the minimum of the y axis.
+pub struct Data2D {
+    name: String,
+    columns: usize,
+    values: Vec<f64>,
+    x: Option<Coordinates>,
+    y: Option<Coordinates>,
+}
+
+/// Position of the cells of a [`Data2D`] along one axis.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Coordinates {
+    /// Cells are spread evenly from the minimum to the maximum
+    Range(f64, f64),
+    /// Boundaries of the cells, one more than cells
+    Edges(Vec<f64>),
+    /// Centres of the cells, one for each cell
+    Centres(Vec<f64>),
+}
+
+impl Data2D {
+    /// The number of values must be a multiple of the number of columns.
+    pub fn new(
+        name: impl Into<String>,
+        columns: usize,
+        values: impl IntoIterator<Item = f64>,
+    ) -> Result<Self> {
+        let name = name.into();
+        let values = values.into_iter().collect::<Vec<_>>();
+        if columns == 0 || values.len() % columns != 0 {
+            return Err(Error::Invalid(format!(
+                "dataset '{name}' with {} values cannot be split into rows of {columns} columns",
+                values.len()
+            )));
+        }
+        Ok(Self {
+            name,
+            columns,
+            values,
+            x: None,
+            y: None,
+        })
+    }
+
+    /// All rows must have the same length.
+    pub fn from_rows(
+        name: impl Into<String>,
+        rows: impl IntoIterator<Item = impl IntoIterator<Item = f64>>,
+    ) -> Result<Self> {
+        let name = name.into();
+        let mut columns = None;
+        let mut values = Vec::new();
+        for row in rows {
+            let len = values.len();
+            values.extend(row);
+            let row_len = values.len() - len;
+            if *columns.get_or_insert(row_len) != row_len {
+                return Err(Error::Invalid(format!(
+                    "dataset '{name}' has rows of different lengths"
+                )));
+            }
+        }
+        Self::new(name, columns.unwrap_or_default(), values)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    pub fn rows(&self) -> usize {
+        self.values.len() / self.columns
+    }
+
+    pub fn set_x(&mut self, coordinates: Coordinates) -> Result<()> {
+        self.check_coordinates("x", &coordinates, self.columns())?;
+        self.x = Some(coordinates);
+        Ok(())
+    }
+
+    pub fn with_x(mut self, coordinates: Coordinates) -> Result<Self> {
+        self.set_x(coordinates)?;
+        Ok(self)
+    }
+
+    pub fn with_x_range(mut self, min: f64, max: f64) -> Self {
+        self.x = Some(Coordinates::Range(min, max));
+        self
+    }
+
+    pub fn set_y(&mut self, coordinates: Coordinates) -> Result<()> {
+        self.check_coordinates("y", &coordinates, self.rows())?;
+        self.y = Some(coordinates);
+        Ok(())
+    }
+
+    pub fn with_y(mut self, coordinates: Coordinates) -> Result<Self> {
+        self.set_y(coordinates)?;
+        Ok(self)
+    }
+
+    pub fn with_y_range(mut self, min: f64, max: f64) -> Self {
+        self.y = Some(Coordinates::Range(min, max));
+        self
+    }
+
+    fn check_coordinates(&self, axis: &str, coordinates: &Coordinates, cells: usize) -> Result<()> {
+        let (kind, expected, actual) = match coordinates {
+            Coordinates::Range(..) => return Ok(()),
+            Coordinates::Edges(edges) => ("edges", cells + 1, edges.len()),
+            Coordinates::Centres(centres) => ("centres", cells, centres.len()),
+        };
+        if expected != actual {
+            return Err(Error::Invalid(format!(
+                "dataset '{}' needs {expected} {axis} {kind} but got {actual}",
+                self.name
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl CommandLineEmbeddingInterface for Data2D {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        let rows = self
+            .values
+            .chunks(self.columns)
+            .map(|row| Column::new(row.iter().copied()).to_string())
+            .collect::<Vec<_>>();
+
+        let mut parameters = Vec::with_capacity(2);
+        for (axis, coordinates) in [("x", &self.x), ("y", &self.y)] {
+            match coordinates {
+                None => {}
+                Some(Coordinates::Range(min, max)) => parameters.push(format!(
+                    "{axis}range=({}, {})",
+                    PyNumber(min),
+                    PyNumber(max)
+                )),
+                Some(Coordinates::Edges(edges)) => {
+                    parameters.push(format!("{axis}edge={}", Column::new(edges.iter().copied())))
+                }
+                Some(Coordinates::Centres(centres)) => parameters.push(format!(
+                    "{axis}cent={}",
+                    Column::new(centres.iter().copied())
+                )),
+            }
+        }
+
+        cmd::SetData2D(&self.name, &PyList(&rows).to_string(), &parameters).write(writer)
+    }
+}
+
+/// A dataset of strings, for example for the labels of bars or points.
+pub struct DataText {
+    name: String,
+    values: Vec<String>,
+}
+
+impl DataText {
+    pub fn new(
+        name: impl Into<String>,
+        values: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            values: values.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+impl CommandLineEmbeddingInterface for DataText {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        cmd::SetDataText(&self.name, &PyStrList(&self.values).to_string()).write(writer)
+    }
+}
+
+/// A dataset of points in time, for example for the x axis of a time series.
+///
+/// `SetDataDateTime` only accepts python `datetime` objects, which cannot be created
+/// by the commands of a document. The values are therefore imported as ISO date-times
+/// with `ImportString`, as veusz itself saves date-time datasets.
+///
+/// The name becomes part of the `name(date)` descriptor of the import and must therefore
+/// not contain whitespace, commas, parentheses or brackets.
+pub struct DataDateTime {
+    name: String,
+    values: Vec<IsoDateTime>,
+}
+
+impl DataDateTime {
+    pub fn new(
+        name: impl Into<String>,
+        values: impl IntoIterator<Item = impl Into<IsoDateTime>>,
+    ) -> Result<Self> {
+        let name = name.into();
+        if name.is_empty()
+            || name
+                .chars()
+                .any(|c| c.is_whitespace() || matches!(c, ',' | '(' | ')' | '[' | ']'))
+        {
+            return Err(Error::Invalid(format!(
+                "date-time dataset name '{name}' cannot be used in an import descriptor"
+            )));
+        }
+        Ok(Self {
+            name,
+            values: values.into_iter().map(Into::into).collect(),
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+impl CommandLineEmbeddingInterface for DataDateTime {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        let mut lines = String::new();
+        for value in &self.values {
+            lines.push_str(&value.to_string());
+            lines.push('\n');
+        }
+        cmd::ImportString(&format!("{}(date)", self.name), &lines).write(writer)
+    }
+}
+
+/// A date and time with microsecond precision and without time zone, as used by veusz.
+/// [`SystemTime`] and the `chrono` types are converted to UTC.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct IsoDateTime {
+    year: i64,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+    microsecond: u32,
+}
+
+impl IsoDateTime {
+    fn from_unix_micros(micros: i128) -> Self {
+        const MICROS_PER_DAY: i128 = 86_400_000_000;
+        let days = micros.div_euclid(MICROS_PER_DAY) as i64;
+        let micros = micros.rem_euclid(MICROS_PER_DAY) as u64;
+        let seconds = (micros / 1_000_000) as u32;
+
+        // days since 1970-01-01 to the proleptic gregorian calendar, see
+        // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+
+        Self {
+            year: yoe + era * 400 + i64::from(month <= 2),
+            month,
+            day: (doy - (153 * mp + 2) / 5 + 1) as u32,
+            hour: seconds / 3_600,
+            minute: seconds / 60 % 60,
+            second: seconds % 60,
+            microsecond: (micros % 1_000_000) as u32,
+        }
+    }
+}
+
+impl Display for IsoDateTime {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )?;
+        if self.microsecond != 0 {
+            write!(f, ".{:06}", self.microsecond)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<SystemTime> for IsoDateTime {
+    fn from(time: SystemTime) -> Self {
+        Self::from_unix_micros(match time.duration_since(UNIX_EPOCH) {
+            Ok(duration) => duration.as_micros() as i128,
+            Err(e) => -(e.duration().as_micros() as i128),
+        })
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::NaiveDateTime> for IsoDateTime {
+    fn from(time: chrono::NaiveDateTime) -> Self {
+        use chrono::{Datelike, Timelike};
+        Self {
+            year: i64::from(time.year()),
+            month: time.month(),
+            day: time.day(),
+            hour: time.hour(),
+            minute: time.minute(),
+            // leap seconds are represented by nanoseconds beyond the second
+            second: time.second(),
+            microsecond: (time.nanosecond() / 1_000).min(999_999),
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::NaiveDate> for IsoDateTime {
+    fn from(date: chrono::NaiveDate) -> Self {
+        date.and_time(chrono::NaiveTime::MIN).into()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<Tz: chrono::TimeZone> From<chrono::DateTime<Tz>> for IsoDateTime {
+    fn from(time: chrono::DateTime<Tz>) -> Self {
+        time.naive_utc().into()
+    }
+}
+
+/// A dataset computed by veusz from an expression of other datasets, like `y * 2`.
+pub struct DataExpression {
+    name: String,
+    expression: String,
+    symerr: Option<String>,
+    negerr: Option<String>,
+    poserr: Option<String>,
+    linked: bool,
+}
+
+impl DataExpression {
+    pub fn new(name: impl Into<String>, expression: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            expression: expression.into(),
+            symerr: None,
+            negerr: None,
+            poserr: None,
+            linked: false,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn set_symerr(&mut self, expression: impl Into<String>) {
+        self.symerr = Some(expression.into());
+    }
+
+    pub fn with_symerr(mut self, expression: impl Into<String>) -> Self {
+        self.set_symerr(expression);
+        self
+    }
+
+    pub fn set_negerr(&mut self, expression: impl Into<String>) {
+        self.negerr = Some(expression.into());
+    }
+
+    pub fn with_negerr(mut self, expression: impl Into<String>) -> Self {
+        self.set_negerr(expression);
+        self
+    }
+
+    pub fn set_poserr(&mut self, expression: impl Into<String>) {
+        self.poserr = Some(expression.into());
+    }
+
+    pub fn with_poserr(mut self, expression: impl Into<String>) -> Self {
+        self.set_poserr(expression);
+        self
+    }
+
+    /// Linked datasets are re-evaluated when the datasets they depend on change, instead
+    /// of being evaluated once.
+    pub fn set_linked(&mut self, linked: bool) {
+        self.linked = linked;
+    }
+
+    pub fn with_linked(mut self, linked: bool) -> Self {
+        self.set_linked(linked);
+        self
+    }
+}
+
+impl CommandLineEmbeddingInterface for DataExpression {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        let mut arguments = vec![
+            PyStr(&self.name).to_string(),
+            PyStr(&self.expression).to_string(),
+        ];
+        for (name, expression) in [
+            ("symerr", &self.symerr),
+            ("negerr", &self.negerr),
+            ("poserr", &self.poserr),
+        ] {
+            if let Some(expression) = expression {
+                arguments.push(format!("{name}={}", PyStr(expression)));
+            }
+        }
+        arguments.push(linked(self.linked));
+        cmd::Call("SetDataExpression", &arguments).write(writer)
+    }
+}
+
+/// A dataset of evenly spaced values, with errors spaced alike.
+pub struct DataRange {
+    name: String,
+    steps: usize,
+    values: (f64, f64),
+    symerr: Option<(f64, f64)>,
+    negerr: Option<(f64, f64)>,
+    poserr: Option<(f64, f64)>,
+    linked: bool,
+}
+
+impl DataRange {
+    /// `steps` values from `start` to `end`, both inclusive.
+    pub fn new(name: impl Into<String>, steps: usize, start: f64, end: f64) -> Self {
+        Self {
+            name: name.into(),
+            steps,
+            values: (start, end),
+            symerr: None,
+            negerr: None,
+            poserr: None,
+            linked: false,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn len(&self) -> usize {
+        self.steps
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps == 0
+    }
+
+    pub fn set_symerr(&mut self, start: f64, end: f64) {
+        self.symerr = Some((start, end));
+    }
+
+    pub fn with_symerr(mut self, start: f64, end: f64) -> Self {
+        self.set_symerr(start, end);
+        self
+    }
+
+    pub fn set_negerr(&mut self, start: f64, end: f64) {
+        self.negerr = Some((start, end));
+    }
+
+    pub fn with_negerr(mut self, start: f64, end: f64) -> Self {
+        self.set_negerr(start, end);
+        self
+    }
+
+    pub fn set_poserr(&mut self, start: f64, end: f64) {
+        self.poserr = Some((start, end));
+    }
+
+    pub fn with_poserr(mut self, start: f64, end: f64) -> Self {
+        self.set_poserr(start, end);
+        self
+    }
+
+    /// Linked datasets are saved as range instead of their values.
+    pub fn set_linked(&mut self, linked: bool) {
+        self.linked = linked;
+    }
+
+    pub fn with_linked(mut self, linked: bool) -> Self {
+        self.set_linked(linked);
+        self
+    }
+}
+
+impl CommandLineEmbeddingInterface for DataRange {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        let mut arguments = vec![
+            PyStr(&self.name).to_string(),
+            self.steps.to_string(),
+            python_tuple(&[self.values.0, self.values.1]),
+        ];
+        for (name, range) in [
+            ("symerr", self.symerr),
+            ("negerr", self.negerr),
+            ("poserr", self.poserr),
+        ] {
+            if let Some((start, end)) = range {
+                arguments.push(format!("{name}={}", python_tuple(&[start, end])));
+            }
+        }
+        arguments.push(linked(self.linked));
+        cmd::Call("SetDataRange", &arguments).write(writer)
+    }
+}
+
+/// A two dimensional dataset computed by veusz from an expression of other two
+/// dimensional datasets.
+pub struct Data2DExpression {
+    name: String,
+    expression: String,
+    linked: bool,
+}
+
+impl Data2DExpression {
+    pub fn new(name: impl Into<String>, expression: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            expression: expression.into(),
+            linked: false,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Linked datasets are re-evaluated when the datasets they depend on change.
+    pub fn set_linked(&mut self, linked: bool) {
+        self.linked = linked;
+    }
+
+    pub fn with_linked(mut self, linked: bool) -> Self {
+        self.set_linked(linked);
+        self
+    }
+}
+
+impl CommandLineEmbeddingInterface for Data2DExpression {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        cmd::Call(
+            "SetData2DExpression",
+            &[
+                PyStr(&self.name).to_string(),
+                PyStr(&self.expression).to_string(),
+                linked(self.linked),
+            ],
+        )
+        .write(writer)
+    }
+}
+
+/// A two dimensional dataset computed by veusz from an expression of `x` and `y`, like
+/// `sin(x) * cos(y)`, evaluated on a grid.
+pub struct Data2DXYFunc {
+    name: String,
+    x: (f64, f64, f64),
+    y: (f64, f64, f64),
+    expression: String,
+    linked: bool,
+}
+
+impl Data2DXYFunc {
+    /// The grid is given by the `(start, stop, step)` of each axis.
+    pub fn new(
+        name: impl Into<String>,
+        x: (f64, f64, f64),
+        y: (f64, f64, f64),
+        expression: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            x,
+            y,
+            expression: expression.into(),
+            linked: false,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Linked datasets are saved as function instead of their values.
+    pub fn set_linked(&mut self, linked: bool) {
+        self.linked = linked;
+    }
+
+    pub fn with_linked(mut self, linked: bool) -> Self {
+        self.set_linked(linked);
+        self
+    }
+}
+
+impl CommandLineEmbeddingInterface for Data2DXYFunc {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        cmd::Call(
+            "SetData2DXYFunc",
+            &[
+                PyStr(&self.name).to_string(),
+                python_tuple(&[self.x.0, self.x.1, self.x.2]),
+                python_tuple(&[self.y.0, self.y.1, self.y.2]),
+                PyStr(&self.expression).to_string(),
+                linked(self.linked),
+            ],
+        )
+        .write(writer)
+    }
+}
+
+fn linked(linked: bool) -> String {
+    format!("linked={}", linked.as_veusz_api1_value_str())
+}
+
+fn python_tuple(values: &[f64]) -> String {
+    let values = values.iter().map(|value| PyNumber(value).to_string());
+    format!("({})", values.collect::<Vec<_>>().join(", "))
+}
+
+/// Numeric values, formatted as the items of a python list.
+pub(crate) struct Column(Vec<String>);
+
+impl Column {
+    pub(crate) fn new(data: impl IntoIterator<Item = impl ToPyNumber>) -> Self {
+        Column(
+            data.into_iter()
+                .map(|value| PyNumber(value).to_string())
+                .collect(),
+        )
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl Display for Column {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        PyList(&self.0).fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api1::{written, written_by};
+
+    #[test]
+    fn set_data_with_errors() {
+        let data = Data::new("y", [1.0, f64::NAN, f64::INFINITY, f64::NEG_INFINITY, -0.5])
+            .with_symerr([0.1, 0.2, 0.3, 0.4, 0.5])
+            .unwrap()
+            .with_poserr([1, 2, 3, 4, 5])
+            .unwrap();
+        assert_eq!(
+            "SetData(u'y', [1, float('nan'), float('inf'), float('-inf'), -0.5], \
+             symerr=[0.1, 0.2, 0.3, 0.4, 0.5], poserr=[1, 2, 3, 4, 5])\n",
+            written(&data, "")
+        );
+    }
+
+    #[test]
+    fn set_data_2d() {
+        let data = Data2D::from_rows("z", [vec![1.0, 2.0], vec![3.0, 4.0]])
+            .unwrap()
+            .with_x_range(0.0, 1.0)
+            .with_y(Coordinates::Edges(vec![0.0, 0.5, 2.0]))
+            .unwrap();
+        assert_eq!(
+            "SetData2D(u'z', [[1, 2], [3, 4]], xrange=(0, 1), yedge=[0, 0.5, 2])\n",
+            written(&data, "")
+        );
+        assert!(Data2D::from_rows("z", [vec![1.0], vec![2.0, 3.0]]).is_err());
+        assert!(Data2D::new("z", 2, [1.0, 2.0, 3.0]).is_err());
+    }
+
+    #[test]
+    fn set_data_text() {
+        let data = DataText::new("labels", ["a", "it's", "line\nbreak"]);
+        assert_eq!(
+            "SetDataText(u'labels', [u'a', u'it\\'s', u'line\\nbreak'])\n",
+            written(&data, "")
+        );
+    }
+
+    #[test]
+    fn date_time_from_system_time() {
+        use std::time::Duration;
+        let data = DataDateTime::new(
+            "t",
+            [
+                UNIX_EPOCH,
+                UNIX_EPOCH + Duration::from_micros(951_825_845_000_250),
+                UNIX_EPOCH - Duration::from_secs(1),
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            "ImportString(u't(date)', u'1970-01-01T00:00:00\\n2000-02-29T12:04:05.000250\\n1969-12-31T23:59:59\\n')\n",
+            written(&data, "")
+        );
+    }
+
+    #[test]
+    fn date_time_name_must_fit_into_descriptor() {
+        for name in ["", "t x", "t,x", "t(date)", "t[1:2]"] {
+            assert!(
+                matches!(
+                    DataDateTime::new(name, [UNIX_EPOCH]),
+                    Err(Error::Invalid(_))
+                ),
+                "{name}"
+            );
+        }
+        assert!(DataDateTime::new("x-time_2", [UNIX_EPOCH]).is_ok());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn date_time_from_chrono() {
+        let date = chrono::NaiveDate::from_ymd_opt(2024, 2, 29).unwrap();
+        assert_eq!("2024-02-29T00:00:00", IsoDateTime::from(date).to_string());
+        let time = date.and_hms_micro_opt(23, 59, 58, 1).unwrap().and_utc();
+        assert_eq!(
+            "2024-02-29T23:59:58.000001",
+            IsoDateTime::from(time).to_string()
+        );
+    }
+
+    #[test]
+    fn expressions_and_ranges() {
+        let written = written_by(|writer| {
+            DataExpression::new("y2", "y_sin * 2")
+                .with_symerr("y_err")
+                .with_linked(true)
+                .write(writer)?;
+            DataRange::new("x", 5, 0.0, 1.0)
+                .with_poserr(0.1, 0.5)
+                .write(writer)?;
+            Data2DExpression::new("z2", "z * 2").write(writer)?;
+            Data2DXYFunc::new("z", (0.0, 1.0, 0.1), (-1.0, 1.0, 0.5), "x * y").write(writer)
+        });
+        assert_eq!(
+            "SetDataExpression(u'y2', u'y_sin * 2', symerr=u'y_err', linked=True)\n\
+             SetDataRange(u'x', 5, (0, 1), poserr=(0.1, 0.5), linked=False)\n\
+             SetData2DExpression(u'z2', u'z * 2', linked=False)\n\
+             SetData2DXYFunc(u'z', (0, 1, 0.1), (-1, 1, 0.5), u'x * y', linked=False)\n",
+            written
+        );
+    }
+
+    #[test]
+    fn mismatching_error_length() {
+        let data = Data::new("y", [1u64, 2]);
+        assert_eq!(2, data.len());
+        assert!(matches!(data.with_negerr([0.1]), Err(Error::Invalid(_))));
     }
 }
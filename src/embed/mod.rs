@@ -0,0 +1,502 @@
+//! Native client for the protocol spoken by `veusz --embed-remote`, as used by the python
+//! `veusz.embed` module: every command is a pickled `(window, command, args, kwargs)` tuple
+//! prefixed by its length and answered synchronously by a pickled return value.
+//!
+//! https://github.com/veusz/veusz/blob/master/veusz/embed.py
+
+use crate::launcher::{Launcher, Process};
+use crate::session::DatasetValues;
+use crate::value::Value;
+use crate::{Error, Result, Veusz};
+use std::collections::hash_map::RandomState;
+use std::fs::File;
+use std::hash::{BuildHasher, Hasher};
+use std::io::{BufWriter, Read, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::path::Path;
+use std::process::{ChildStdin, Command, ExitStatus, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+pub(crate) mod pickle;
+
+/// Any bidirectional byte stream the protocol can be spoken over.
+pub trait Stream: Read + Write + Send {}
+
+impl<T: Read + Write + Send> Stream for T {}
+
+struct Connection {
+    stream: Box<dyn Stream>,
+}
+
+impl Connection {
+    fn send(&mut self, command: &Value) -> Result<Value> {
+        let data = pickle::dumps(command);
+        let len = u32::try_from(data.len())
+            .map_err(|_| Error::Protocol("command exceeds the maximum length".into()))?;
+        let data = self.exchange(len, &data).map_err(timed_out)?;
+
+        match pickle::loads(&data)? {
+            Value::Object { class, args, .. } if is_exception(&class) => {
+                let message = args
+                    .as_slice()
+                    .and_then(|args| args.first())
+                    .and_then(Value::as_str)
+                    .unwrap_or_default();
+                Err(Error::Command(format!("{class}: {message}")))
+            }
+            value => Ok(value),
+        }
+    }
+}
+
+impl Connection {
+    fn exchange(&mut self, len: u32, data: &[u8]) -> std::io::Result<Vec<u8>> {
+        self.stream.write_all(&len.to_le_bytes())?;
+        self.stream.write_all(data)?;
+        self.stream.flush()?;
+
+        let mut len = [0u8; 4];
+        self.stream.read_exact(&mut len)?;
+        let mut data = vec![0u8; u32::from_le_bytes(len) as usize];
+        self.stream.read_exact(&mut data)?;
+        Ok(data)
+    }
+}
+
+/// Sockets report an elapsed read or write timeout as `WouldBlock` on unix.
+fn timed_out(error: std::io::Error) -> std::io::Error {
+    match error.kind() {
+        std::io::ErrorKind::WouldBlock => std::io::Error::new(
+            std::io::ErrorKind::TimedOut,
+            "veusz did not answer within the timeout",
+        ),
+        _ => error,
+    }
+}
+
+fn is_exception(class: &str) -> bool {
+    let name = class.rsplit('.').next().unwrap_or(class);
+    name.ends_with("Error") || name.ends_with("Exception")
+}
+
+fn command(window: i64, command: &str, args: Vec<Value>, kwargs: Vec<(&str, Value)>) -> Value {
+    Value::Tuple(vec![
+        Value::Int(window),
+        Value::Str(command.to_string()),
+        Value::Tuple(args),
+        Value::Dict(
+            kwargs
+                .into_iter()
+                .map(|(key, value)| (Value::Str(key.to_string()), value))
+                .collect(),
+        ),
+    ])
+}
+
+/// Connection to a `veusz --embed-remote` process, which can host several [`Window`]s.
+pub struct EmbedRemote {
+    process: Option<(Process, ChildStdin)>,
+    connection: Arc<Mutex<Connection>>,
+}
+
+impl EmbedRemote {
+    /// Starts `veusz --embed-remote` and connects to it.
+    pub fn start() -> Result<Self> {
+        Self::start_with(&Launcher::default())
+    }
+
+    /// Starts `veusz --embed-remote` as configured by the launcher and connects to it.
+    pub fn start_with(launcher: &Launcher) -> Result<Self> {
+        launcher
+            .spawn(launcher.command(&["--embed-remote"]))
+            .and_then(Self::connect)
+    }
+
+    /// Starts the given command, which must behave like `veusz --embed-remote`, and
+    /// connects to it through a local TCP socket.
+    pub fn spawn(mut command: Command) -> Result<Self> {
+        let process = command
+            .stdin(Stdio::piped())
+            .spawn()
+            .map_err(|e| Error::spawn(command.get_program(), e))?;
+        Self::connect(Process::from_child(process))
+    }
+
+    fn connect(mut process: Process) -> Result<Self> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+        let port = listener.local_addr()?.port();
+        let mut stdin = process.take_stdin()?;
+
+        writeln!(stdin, "internet {} {port}", Ipv4Addr::LOCALHOST)?;
+        stdin.flush()?;
+
+        let mut stream = accept(&listener, &mut process)?;
+
+        // the remote process echos a secret received through stdin over the socket, to
+        // verify that no other process connected to the port
+        let secret = format!("{}\n", secret());
+        stdin.write_all(secret.as_bytes())?;
+        stdin.flush()?;
+
+        let mut secret_back = vec![0u8; secret.len()];
+        stream.read_exact(&mut secret_back).map_err(timed_out)?;
+        if secret.as_bytes() != secret_back {
+            return Err(Error::Protocol(
+                "secret sent back by the veusz process does not match".into(),
+            ));
+        }
+
+        Ok(Self {
+            process: Some((process, stdin)),
+            connection: Arc::new(Mutex::new(Connection {
+                stream: Box::new(stream),
+            })),
+        })
+    }
+
+    /// Speaks the protocol over an already established stream, for example to a remote
+    /// process started by other means.
+    pub fn with_stream(stream: impl Stream + 'static) -> Self {
+        Self {
+            process: None,
+            connection: Arc::new(Mutex::new(Connection {
+                stream: Box::new(stream),
+            })),
+        }
+    }
+
+    pub fn new_window(&self, name: &str) -> Result<Window> {
+        self.open_window(name, false)
+    }
+
+    /// Creates a window which is not shown, for example to only export plots.
+    pub fn new_hidden_window(&self, name: &str) -> Result<Window> {
+        self.open_window(name, true)
+    }
+
+    fn open_window(&self, name: &str, hidden: bool) -> Result<Window> {
+        let reply = send(
+            &self.connection,
+            command(
+                -1,
+                "_NewWindow",
+                vec![name.into()],
+                vec![("hidden", hidden.into())],
+            ),
+        )?;
+
+        // the reply consists of the window number and the available commands
+        match reply.as_slice().and_then(|reply| reply.first()?.as_i64()) {
+            Some(number) => Ok(Window {
+                number,
+                connection: Arc::clone(&self.connection),
+            }),
+            None => Err(Error::Protocol(format!(
+                "expected a window number, got {reply:?}"
+            ))),
+        }
+    }
+
+    /// Closes all windows, terminates the remote process and waits for it to exit.
+    pub fn quit(mut self) -> Result<Option<ExitStatus>> {
+        match send(&self.connection, command(-1, "_Quit", vec![], vec![])) {
+            // the remote process might exit before replying
+            Err(Error::Io(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => {}
+            result => drop(result?),
+        }
+        match self.process.take() {
+            Some((mut process, stdin)) => {
+                drop(stdin);
+                process.wait().map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+fn send(connection: &Mutex<Connection>, command: Value) -> Result<Value> {
+    connection
+        .lock()
+        .map_err(|_| Error::Protocol("connection poisoned by a previous panic".into()))?
+        .send(&command)
+}
+
+/// Waits for the process to connect, at most as long as the timeout of the process, which
+/// then also limits every read and write on the connection.
+fn accept(listener: &TcpListener, process: &mut Process) -> Result<TcpStream> {
+    let start = Instant::now();
+    listener.set_nonblocking(true)?;
+    loop {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                stream.set_nonblocking(false)?;
+                stream.set_read_timeout(process.timeout())?;
+                stream.set_write_timeout(process.timeout())?;
+                return Ok(stream);
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                if process.try_wait()?.is_some() {
+                    process.wait()?;
+                    return Err(Error::Protocol("veusz exited before connecting".into()));
+                }
+                if process
+                    .timeout()
+                    .is_some_and(|timeout| start.elapsed() >= timeout)
+                {
+                    process.kill();
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::TimedOut,
+                        "veusz did not connect within the timeout",
+                    )
+                    .into());
+                }
+                std::thread::sleep(Duration::from_millis(10));
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+}
+
+fn secret() -> String {
+    let state = RandomState::new();
+    (0..16)
+        .map(|i| {
+            let mut hasher = state.build_hasher();
+            hasher.write_usize(i);
+            char::from(b'a' + (hasher.finish() % 26) as u8)
+        })
+        .collect()
+}
+
+/// A plot window of an [`EmbedRemote`] process. Each window has its own document.
+pub struct Window {
+    number: i64,
+    connection: Arc<Mutex<Connection>>,
+}
+
+impl Window {
+    pub fn number(&self) -> i64 {
+        self.number
+    }
+
+    /// Executes a veusz command (`SetData`, `Add`, `Export`, ...) or window method
+    /// (`ResizeWindow`, `MoveToPage`, ...) and returns its result.
+    pub fn call(&self, name: &str, args: Vec<Value>, kwargs: Vec<(&str, Value)>) -> Result<Value> {
+        send(&self.connection, command(self.number, name, args, kwargs))
+    }
+
+    pub fn set(&self, path: &str, value: impl Into<Value>) -> Result<()> {
+        self.call("Set", vec![path.into(), value.into()], vec![])
+            .map(drop)
+    }
+
+    /// Transfers the values in binary form without formatting them as text.
+    pub fn set_data(&self, name: &str, values: impl Into<Vec<f64>>) -> Result<()> {
+        self.call(
+            "SetData",
+            vec![name.into(), Value::Array(values.into())],
+            vec![],
+        )
+        .map(drop)
+    }
+
+    pub fn get(&self, path: &str) -> Result<Value> {
+        self.call("Get", vec![path.into()], vec![])
+    }
+
+    /// The names of all datasets of the document.
+    pub fn get_datasets(&self) -> Result<Vec<String>> {
+        self.call("GetDatasets", vec![], vec![])?.into_strings()
+    }
+
+    /// The values and errors of a numeric, one dimensional dataset.
+    pub fn get_data(&self, name: &str) -> Result<DatasetValues> {
+        let reply = self.call("GetData", vec![name.into()], vec![])?;
+        DatasetValues::from_reply(reply)
+    }
+
+    pub fn load(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref().to_string_lossy();
+        self.call("Load", vec![path.as_ref().into()], vec![])
+            .map(drop)
+    }
+
+    /// Sends the data, pages and exports of the given document by loading it from a
+    /// temporary file.
+    pub fn send(&self, veusz: &Veusz) -> Result<()> {
+        let path = crate::temporary_path("vsz");
+        let result = (|| {
+            let mut writer = BufWriter::new(File::create(&path)?);
+            veusz.save_configuration(&mut writer)?;
+            writer.flush()?;
+            drop(writer);
+            self.load(&path)
+        })();
+        let _ = std::fs::remove_file(&path);
+        result
+    }
+
+    pub fn export(&self, filename: &str) -> Result<()> {
+        self.call("Export", vec![filename.into()], vec![]).map(drop)
+    }
+
+    pub fn resize_window(&self, width: i64, height: i64) -> Result<()> {
+        self.call("ResizeWindow", vec![width.into(), height.into()], vec![])
+            .map(drop)
+    }
+
+    pub fn close(self) -> Result<()> {
+        self.call("_Close", vec![], vec![]).map(drop)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread::JoinHandle;
+
+    /// Stands in for `veusz --embed-remote` by answering commands with canned replies
+    /// and recording the datasets it receives.
+    fn mock_server() -> (EmbedRemote, JoinHandle<Vec<Value>>) {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (mut server, _) = listener.accept().unwrap();
+
+        let handle = std::thread::spawn(move || {
+            let mut received = Vec::new();
+            let mut windows = 0;
+            loop {
+                let mut len = [0u8; 4];
+                server.read_exact(&mut len).unwrap();
+                let mut data = vec![0u8; u32::from_le_bytes(len) as usize];
+                server.read_exact(&mut data).unwrap();
+
+                let request = pickle::loads(&data).unwrap();
+                let request = request.as_slice().unwrap().to_vec();
+                let [window, name, args, _kwargs] = &request[..] else {
+                    panic!("malformed request: {request:?}");
+                };
+                let reply = match name.as_str().unwrap() {
+                    "_NewWindow" => {
+                        windows += 1;
+                        Value::Tuple(vec![Value::Int(windows), Value::List(vec![])])
+                    }
+                    "SetData" => {
+                        received.push(Value::Tuple(vec![window.clone(), args.clone()]));
+                        Value::None
+                    }
+                    "GetData" => Value::Tuple(vec![
+                        Value::List(vec![Value::Float(1.0), Value::Int(2)]),
+                        Value::None,
+                        Value::List(vec![Value::Float(0.5), Value::Float(0.5)]),
+                        Value::None,
+                    ]),
+                    "Get" => Value::Str(format!("window {}", window.as_i64().unwrap())),
+                    "_Close" => Value::None,
+                    "_Quit" => {
+                        send_reply(&mut server, &Value::None);
+                        return received;
+                    }
+                    other => Value::Object {
+                        class: "builtins.AttributeError".into(),
+                        args: Box::new(Value::Tuple(vec![Value::Str(format!(
+                            "unknown command {other}"
+                        ))])),
+                        state: None,
+                    },
+                };
+                send_reply(&mut server, &reply);
+            }
+        });
+
+        (EmbedRemote::with_stream(client), handle)
+    }
+
+    fn send_reply(server: &mut TcpStream, reply: &Value) {
+        let data = pickle::dumps(reply);
+        server
+            .write_all(&(data.len() as u32).to_le_bytes())
+            .unwrap();
+        server.write_all(&data).unwrap();
+    }
+
+    #[test]
+    fn multiple_windows() {
+        let (remote, server) = mock_server();
+        let first = remote.new_window("first").unwrap();
+        let second = remote.new_hidden_window("second").unwrap();
+        assert_eq!(1, first.number());
+        assert_eq!(2, second.number());
+        assert_eq!(Value::Str("window 2".into()), second.get("/").unwrap());
+        first.close().unwrap();
+        assert!(remote.quit().unwrap().is_none());
+        assert!(server.join().unwrap().is_empty());
+    }
+
+    #[test]
+    fn binary_datasets_and_return_values() {
+        let (remote, server) = mock_server();
+        let window = remote.new_window("data").unwrap();
+        window.set_data("x", vec![1.0, 2.0]).unwrap();
+        assert_eq!(
+            DatasetValues {
+                values: vec![1.0, 2.0],
+                symerr: None,
+                poserr: Some(vec![0.5, 0.5]),
+                negerr: None,
+            },
+            window.get_data("x").unwrap()
+        );
+        remote.quit().unwrap();
+
+        let received = server.join().unwrap();
+        let mut bytes = 1.0f64.to_le_bytes().to_vec();
+        bytes.extend_from_slice(&2.0f64.to_le_bytes());
+        assert_eq!(
+            vec![Value::Tuple(vec![
+                Value::Int(1),
+                Value::Tuple(vec![
+                    Value::Str("x".into()),
+                    Value::Object {
+                        class: "numpy.frombuffer".into(),
+                        args: Box::new(Value::Tuple(vec![
+                            Value::Bytes(bytes),
+                            Value::Str("<f8".into())
+                        ])),
+                        state: None,
+                    }
+                ])
+            ])],
+            received
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn connecting_times_out() {
+        let launcher = Launcher::default()
+            .with_executable("sh")
+            .with_args(["-c", "sleep 10"])
+            .with_timeout(Duration::from_millis(50));
+        match EmbedRemote::start_with(&launcher) {
+            Err(Error::Io(e)) => assert_eq!(std::io::ErrorKind::TimedOut, e.kind()),
+            Err(other) => panic!("unexpected error: {other:?}"),
+            Ok(_) => panic!("connected without a veusz process"),
+        }
+    }
+
+    #[test]
+    fn exceptions_are_errors() {
+        let (remote, server) = mock_server();
+        let window = remote.new_window("errors").unwrap();
+        match window.call("Bogus", vec![], vec![]) {
+            Err(Error::Command(message)) => {
+                assert_eq!("builtins.AttributeError: unknown command Bogus", message)
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
+        remote.quit().unwrap();
+        server.join().unwrap();
+    }
+}
@@ -0,0 +1,810 @@
+//! Minimal implementation of the python pickle format as far as it is required to talk
+//! to `veusz --embed-remote`.
+//!
+//! https://github.com/python/cpython/blob/main/Lib/pickletools.py
+
+use crate::value::Value;
+use crate::{Error, Result};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+const PROTO: u8 = 0x80;
+const FRAME: u8 = 0x95;
+const STOP: u8 = b'.';
+const MARK: u8 = b'(';
+const POP: u8 = b'0';
+const POP_MARK: u8 = b'1';
+const DUP: u8 = b'2';
+const NONE: u8 = b'N';
+const NEWTRUE: u8 = 0x88;
+const NEWFALSE: u8 = 0x89;
+const INT: u8 = b'I';
+const LONG: u8 = b'L';
+const BININT: u8 = b'J';
+const BININT1: u8 = b'K';
+const BININT2: u8 = b'M';
+const LONG1: u8 = 0x8a;
+const LONG4: u8 = 0x8b;
+const FLOAT: u8 = b'F';
+const BINFLOAT: u8 = b'G';
+const BINSTRING: u8 = b'T';
+const SHORT_BINSTRING: u8 = b'U';
+const UNICODE: u8 = b'V';
+const BINUNICODE: u8 = b'X';
+const SHORT_BINUNICODE: u8 = 0x8c;
+const BINUNICODE8: u8 = 0x8d;
+const BINBYTES: u8 = b'B';
+const SHORT_BINBYTES: u8 = b'C';
+const BINBYTES8: u8 = 0x8e;
+const BYTEARRAY8: u8 = 0x96;
+const EMPTY_LIST: u8 = b']';
+const APPEND: u8 = b'a';
+const APPENDS: u8 = b'e';
+const LIST: u8 = b'l';
+const EMPTY_TUPLE: u8 = b')';
+const TUPLE: u8 = b't';
+const TUPLE1: u8 = 0x85;
+const TUPLE2: u8 = 0x86;
+const TUPLE3: u8 = 0x87;
+const EMPTY_DICT: u8 = b'}';
+const DICT: u8 = b'd';
+const SETITEM: u8 = b's';
+const SETITEMS: u8 = b'u';
+const EMPTY_SET: u8 = 0x8f;
+const ADDITEMS: u8 = 0x90;
+const FROZENSET: u8 = 0x91;
+const GLOBAL: u8 = b'c';
+const STACK_GLOBAL: u8 = 0x93;
+const REDUCE: u8 = b'R';
+const BUILD: u8 = b'b';
+const NEWOBJ: u8 = 0x81;
+const NEWOBJ_EX: u8 = 0x92;
+const PUT: u8 = b'p';
+const BINPUT: u8 = b'q';
+const LONG_BINPUT: u8 = b'r';
+const MEMOIZE: u8 = 0x94;
+const GET: u8 = b'g';
+const BINGET: u8 = b'h';
+const LONG_BINGET: u8 = b'j';
+
+/// Serializes the value with pickle protocol 3. [`Value::Array`] is sent as raw little
+/// endian bytes which are turned into an array by `numpy.frombuffer` on the remote side.
+pub(crate) fn dumps(value: &Value) -> Vec<u8> {
+    let mut out = vec![PROTO, 3];
+    encode(value, &mut out);
+    out.push(STOP);
+    out
+}
+
+fn encode(value: &Value, out: &mut Vec<u8>) {
+    match value {
+        Value::None => out.push(NONE),
+        Value::Bool(true) => out.push(NEWTRUE),
+        Value::Bool(false) => out.push(NEWFALSE),
+        Value::Int(value) => {
+            if let Ok(value) = i32::try_from(*value) {
+                out.push(BININT);
+                out.extend_from_slice(&value.to_le_bytes());
+            } else {
+                out.push(LONG1);
+                out.push(8);
+                out.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+        Value::Float(value) => {
+            out.push(BINFLOAT);
+            out.extend_from_slice(&value.to_be_bytes());
+        }
+        Value::Str(value) => {
+            out.push(BINUNICODE);
+            out.extend_from_slice(&(value.len() as u32).to_le_bytes());
+            out.extend_from_slice(value.as_bytes());
+        }
+        Value::Bytes(value) => {
+            out.push(BINBYTES);
+            out.extend_from_slice(&(value.len() as u32).to_le_bytes());
+            out.extend_from_slice(value);
+        }
+        Value::List(values) => {
+            out.push(EMPTY_LIST);
+            if !values.is_empty() {
+                out.push(MARK);
+                values.iter().for_each(|value| encode(value, out));
+                out.push(APPENDS);
+            }
+        }
+        Value::Tuple(values) => {
+            out.push(MARK);
+            values.iter().for_each(|value| encode(value, out));
+            out.push(TUPLE);
+        }
+        Value::Dict(entries) => {
+            out.push(EMPTY_DICT);
+            if !entries.is_empty() {
+                out.push(MARK);
+                for (key, value) in entries {
+                    encode(key, out);
+                    encode(value, out);
+                }
+                out.push(SETITEMS);
+            }
+        }
+        Value::Array(values) => {
+            let bytes = values
+                .iter()
+                .flat_map(|value| value.to_le_bytes())
+                .collect::<Vec<u8>>();
+            encode_global("numpy.frombuffer", out);
+            encode(
+                &Value::Tuple(vec![Value::Bytes(bytes), Value::Str("<f8".into())]),
+                out,
+            );
+            out.push(REDUCE);
+        }
+        Value::Object { class, args, state } => {
+            encode_global(class, out);
+            encode(args, out);
+            out.push(REDUCE);
+            if let Some(state) = state {
+                encode(state, out);
+                out.push(BUILD);
+            }
+        }
+    }
+}
+
+fn encode_global(class: &str, out: &mut Vec<u8>) {
+    let (module, name) = class.rsplit_once('.').unwrap_or(("builtins", class));
+    out.push(GLOBAL);
+    out.extend_from_slice(module.as_bytes());
+    out.push(b'\n');
+    out.extend_from_slice(name.as_bytes());
+    out.push(b'\n');
+}
+
+/// Deserializes a pickle of any protocol version. Objects are not instantiated but
+/// returned as [`Value::Object`], except for numpy arrays of numbers which are converted
+/// to [`Value::Array`] (or nested lists thereof for multi dimensional arrays).
+pub(crate) fn loads(data: &[u8]) -> Result<Value> {
+    Unpickler {
+        data,
+        position: 0,
+        stack: Vec::new(),
+        marks: Vec::new(),
+        memo: Vec::new(),
+    }
+    .load()
+}
+
+/// Intermediate representation which allows containers to be shared through the memo
+/// while they are still being filled.
+#[derive(Clone)]
+enum Item {
+    Value(Value),
+    List(Rc<RefCell<Vec<Item>>>),
+    Tuple(Rc<Vec<Item>>),
+    Dict(Rc<RefCell<Vec<(Item, Item)>>>),
+    Global(String),
+    Object(Rc<RefCell<Object>>),
+}
+
+struct Object {
+    class: String,
+    args: Item,
+    state: Option<Item>,
+}
+
+impl Item {
+    fn into_value(self) -> Value {
+        match self {
+            Item::Value(value) => value,
+            Item::List(items) => Value::List(
+                items
+                    .borrow()
+                    .iter()
+                    .cloned()
+                    .map(Item::into_value)
+                    .collect(),
+            ),
+            Item::Tuple(items) => {
+                Value::Tuple(items.iter().cloned().map(Item::into_value).collect())
+            }
+            Item::Dict(entries) => Value::Dict(
+                entries
+                    .borrow()
+                    .iter()
+                    .cloned()
+                    .map(|(key, value)| (key.into_value(), value.into_value()))
+                    .collect(),
+            ),
+            Item::Global(class) => Value::Object {
+                class,
+                args: Box::new(Value::Tuple(Vec::new())),
+                state: None,
+            },
+            Item::Object(object) => {
+                let object = object.borrow();
+                let args = object.args.clone().into_value();
+                let state = object.state.clone().map(Item::into_value);
+                numpy_array(&object.class, &args, state.as_ref()).unwrap_or_else(|| Value::Object {
+                    class: object.class.clone(),
+                    args: Box::new(args),
+                    state: state.map(Box::new),
+                })
+            }
+        }
+    }
+}
+
+/// Converts the reduced form of a numpy array back into numbers if possible.
+fn numpy_array(class: &str, args: &Value, state: Option<&Value>) -> Option<Value> {
+    let (shape, dtype, data) = match class {
+        "numpy.core.multiarray._reconstruct" | "numpy._core.multiarray._reconstruct" => {
+            // state: (version, shape, dtype, is_fortran, data)
+            let state = state?.as_slice()?;
+            if state.get(3)?.as_bool()? {
+                return None;
+            }
+            (state.get(1)?, state.get(2)?, state.get(4)?)
+        }
+        "numpy.core.numeric._frombuffer" | "numpy._core.numeric._frombuffer" => {
+            // args: (buffer, dtype, shape, order)
+            let args = args.as_slice()?;
+            if args.get(3)?.as_str()? != "C" {
+                return None;
+            }
+            (args.get(2)?, args.get(1)?, args.first()?)
+        }
+        _ => return None,
+    };
+
+    let Value::Bytes(data) = data else {
+        return None;
+    };
+    let shape = shape
+        .as_slice()?
+        .iter()
+        .map(|dim| dim.as_i64().and_then(|dim| usize::try_from(dim).ok()))
+        .collect::<Option<Vec<usize>>>()?;
+    let values = numpy_values(dtype, data)?;
+    if shape.iter().product::<usize>() != values.len() {
+        return None;
+    }
+    Some(reshape(&shape, &values))
+}
+
+fn numpy_values(dtype: &Value, data: &[u8]) -> Option<Vec<f64>> {
+    let Value::Object { class, args, state } = dtype else {
+        return None;
+    };
+    if !class.ends_with("dtype") {
+        return None;
+    }
+    let descr = args.as_slice()?.first()?.as_str()?;
+    // state: (version, byteorder, ...)
+    let big_endian = match state
+        .as_ref()
+        .and_then(|state| state.as_slice()?.get(1)?.as_str())
+    {
+        Some(">") => true,
+        Some("<" | "|") => false,
+        _ => cfg!(target_endian = "big"),
+    };
+
+    macro_rules! convert {
+        ($ty:ty) => {{
+            const SIZE: usize = std::mem::size_of::<$ty>();
+            data.chunks_exact(SIZE)
+                .map(|chunk| {
+                    let bytes: [u8; SIZE] = chunk.try_into().ok()?;
+                    Some(if big_endian {
+                        <$ty>::from_be_bytes(bytes) as f64
+                    } else {
+                        <$ty>::from_le_bytes(bytes) as f64
+                    })
+                })
+                .collect()
+        }};
+    }
+
+    match descr {
+        "f8" => convert!(f64),
+        "f4" => convert!(f32),
+        "i8" => convert!(i64),
+        "i4" => convert!(i32),
+        "i2" => convert!(i16),
+        "i1" => convert!(i8),
+        "u8" => convert!(u64),
+        "u4" => convert!(u32),
+        "u2" => convert!(u16),
+        "u1" | "b1" => convert!(u8),
+        _ => None,
+    }
+}
+
+fn reshape(shape: &[usize], values: &[f64]) -> Value {
+    match shape {
+        [] | [_] => Value::Array(values.to_vec()),
+        [first, rest @ ..] => {
+            let chunk = rest.iter().product::<usize>();
+            Value::List(
+                (0..*first)
+                    .map(|i| reshape(rest, &values[i * chunk..(i + 1) * chunk]))
+                    .collect(),
+            )
+        }
+    }
+}
+
+struct Unpickler<'a> {
+    data: &'a [u8],
+    position: usize,
+    stack: Vec<Item>,
+    marks: Vec<usize>,
+    memo: Vec<Option<Item>>,
+}
+
+impl<'a> Unpickler<'a> {
+    fn error(&self, message: impl AsRef<str>) -> Error {
+        Error::Protocol(format!(
+            "invalid pickle at {}: {}",
+            self.position,
+            message.as_ref()
+        ))
+    }
+
+    fn read(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self
+            .position
+            .checked_add(len)
+            .filter(|end| *end <= self.data.len())
+            .ok_or_else(|| self.error("unexpected end of data"))?;
+        let bytes = &self.data[self.position..end];
+        self.position = end;
+        Ok(bytes)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        let mut array = [0u8; N];
+        array.copy_from_slice(self.read(N)?);
+        Ok(array)
+    }
+
+    fn read_u8(&mut self) -> Result<u8> {
+        Ok(self.read(1)?[0])
+    }
+
+    fn read_u32(&mut self) -> Result<usize> {
+        Ok(u32::from_le_bytes(self.read_array()?) as usize)
+    }
+
+    fn read_u64(&mut self) -> Result<usize> {
+        usize::try_from(u64::from_le_bytes(self.read_array()?))
+            .map_err(|_| self.error("length out of range"))
+    }
+
+    fn read_line(&mut self) -> Result<&'a str> {
+        let remaining = &self.data[self.position..];
+        let len = remaining
+            .iter()
+            .position(|b| *b == b'\n')
+            .ok_or_else(|| self.error("unterminated line"))?;
+        let line = self.read(len)?;
+        self.position += 1;
+        std::str::from_utf8(line).map_err(|_| self.error("line is not valid utf-8"))
+    }
+
+    fn read_string(&mut self, len: usize) -> Result<Item> {
+        let bytes = self.read(len)?;
+        String::from_utf8(bytes.to_vec())
+            .map(|string| Item::Value(Value::Str(string)))
+            .map_err(|_| self.error("string is not valid utf-8"))
+    }
+
+    fn read_bytes(&mut self, len: usize) -> Result<Item> {
+        Ok(Item::Value(Value::Bytes(self.read(len)?.to_vec())))
+    }
+
+    fn pop(&mut self) -> Result<Item> {
+        self.stack
+            .pop()
+            .ok_or_else(|| self.error("stack underflow"))
+    }
+
+    fn top(&self) -> Result<&Item> {
+        self.stack
+            .last()
+            .ok_or_else(|| self.error("stack underflow"))
+    }
+
+    fn pop_mark(&mut self) -> Result<Vec<Item>> {
+        let mark = self.marks.pop().ok_or_else(|| self.error("missing mark"))?;
+        if mark > self.stack.len() {
+            return Err(self.error("mark beyond stack"));
+        }
+        Ok(self.stack.split_off(mark))
+    }
+
+    fn memo_put(&mut self, index: usize) -> Result<()> {
+        let item = self.top()?.clone();
+        if self.memo.len() <= index {
+            self.memo.resize(index + 1, None);
+        }
+        self.memo[index] = Some(item);
+        Ok(())
+    }
+
+    fn memo_get(&mut self, index: usize) -> Result<()> {
+        let item = self
+            .memo
+            .get(index)
+            .cloned()
+            .flatten()
+            .ok_or_else(|| self.error(format!("memo {index} not found")))?;
+        self.stack.push(item);
+        Ok(())
+    }
+
+    fn append(&mut self, items: Vec<Item>) -> Result<()> {
+        match self.top()? {
+            Item::List(list) => {
+                list.borrow_mut().extend(items);
+                Ok(())
+            }
+            _ => Err(self.error("append to non-list")),
+        }
+    }
+
+    fn set_items(&mut self, items: Vec<Item>) -> Result<()> {
+        if !items.len().is_multiple_of(2) {
+            return Err(self.error("odd number of dict items"));
+        }
+        match self.top()? {
+            Item::Dict(dict) => {
+                let mut dict = dict.borrow_mut();
+                let mut items = items.into_iter();
+                while let (Some(key), Some(value)) = (items.next(), items.next()) {
+                    dict.push((key, value));
+                }
+                Ok(())
+            }
+            _ => Err(self.error("set item on non-dict")),
+        }
+    }
+
+    fn reduce(&mut self, args: Item) -> Result<()> {
+        let class = match self.pop()? {
+            Item::Global(class) => class,
+            Item::Object(object) => object.borrow().class.clone(),
+            _ => return Err(self.error("callable is not a global")),
+        };
+        self.stack.push(Item::Object(Rc::new(RefCell::new(Object {
+            class,
+            args,
+            state: None,
+        }))));
+        Ok(())
+    }
+
+    fn load(mut self) -> Result<Value> {
+        loop {
+            let opcode = self.read_u8()?;
+            match opcode {
+                PROTO => {
+                    self.read_u8()?;
+                }
+                FRAME => {
+                    self.read(8)?;
+                }
+                STOP => return Ok(self.pop()?.into_value()),
+                MARK => self.marks.push(self.stack.len()),
+                POP => {
+                    self.pop()?;
+                }
+                POP_MARK => {
+                    self.pop_mark()?;
+                }
+                DUP => self.stack.push(self.top()?.clone()),
+                NONE => self.stack.push(Item::Value(Value::None)),
+                NEWTRUE => self.stack.push(Item::Value(Value::Bool(true))),
+                NEWFALSE => self.stack.push(Item::Value(Value::Bool(false))),
+                INT | LONG => {
+                    let line = self.read_line()?.trim_end_matches('L');
+                    let value = match line {
+                        "00" => Value::Bool(false),
+                        "01" => Value::Bool(true),
+                        line => line
+                            .parse::<i64>()
+                            .map(Value::Int)
+                            .map_err(|_| self.error("invalid integer"))?,
+                    };
+                    self.stack.push(Item::Value(value));
+                }
+                BININT => {
+                    let value = i32::from_le_bytes(self.read_array()?);
+                    self.stack.push(Item::Value(Value::Int(value.into())));
+                }
+                BININT1 => {
+                    let value = self.read_u8()?;
+                    self.stack.push(Item::Value(Value::Int(value.into())));
+                }
+                BININT2 => {
+                    let value = u16::from_le_bytes(self.read_array()?);
+                    self.stack.push(Item::Value(Value::Int(value.into())));
+                }
+                LONG1 | LONG4 => {
+                    let len = match opcode {
+                        LONG1 => self.read_u8()? as usize,
+                        _ => self.read_u32()?,
+                    };
+                    let bytes = self.read(len)?;
+                    if len > 8 {
+                        return Err(self.error("integer out of range"));
+                    }
+                    let negative = bytes.last().is_some_and(|b| b & 0x80 != 0);
+                    let mut buffer = [if negative { 0xff } else { 0 }; 8];
+                    buffer[..len].copy_from_slice(bytes);
+                    self.stack
+                        .push(Item::Value(Value::Int(i64::from_le_bytes(buffer))));
+                }
+                FLOAT => {
+                    let value = self
+                        .read_line()?
+                        .parse::<f64>()
+                        .map_err(|_| self.error("invalid float"))?;
+                    self.stack.push(Item::Value(Value::Float(value)));
+                }
+                BINFLOAT => {
+                    let value = f64::from_be_bytes(self.read_array()?);
+                    self.stack.push(Item::Value(Value::Float(value)));
+                }
+                SHORT_BINSTRING | SHORT_BINUNICODE => {
+                    let len = self.read_u8()? as usize;
+                    let item = self.read_string(len)?;
+                    self.stack.push(item);
+                }
+                BINSTRING | BINUNICODE => {
+                    let len = self.read_u32()?;
+                    let item = self.read_string(len)?;
+                    self.stack.push(item);
+                }
+                BINUNICODE8 => {
+                    let len = self.read_u64()?;
+                    let item = self.read_string(len)?;
+                    self.stack.push(item);
+                }
+                UNICODE => {
+                    let line = self.read_line()?.to_string();
+                    self.stack.push(Item::Value(Value::Str(line)));
+                }
+                SHORT_BINBYTES => {
+                    let len = self.read_u8()? as usize;
+                    let item = self.read_bytes(len)?;
+                    self.stack.push(item);
+                }
+                BINBYTES => {
+                    let len = self.read_u32()?;
+                    let item = self.read_bytes(len)?;
+                    self.stack.push(item);
+                }
+                BINBYTES8 | BYTEARRAY8 => {
+                    let len = self.read_u64()?;
+                    let item = self.read_bytes(len)?;
+                    self.stack.push(item);
+                }
+                EMPTY_LIST | EMPTY_SET => self.stack.push(Item::List(Default::default())),
+                LIST | FROZENSET => {
+                    let items = self.pop_mark()?;
+                    self.stack.push(Item::List(Rc::new(RefCell::new(items))));
+                }
+                APPEND => {
+                    let item = self.pop()?;
+                    self.append(vec![item])?;
+                }
+                APPENDS | ADDITEMS => {
+                    let items = self.pop_mark()?;
+                    self.append(items)?;
+                }
+                EMPTY_TUPLE => self.stack.push(Item::Tuple(Default::default())),
+                TUPLE => {
+                    let items = self.pop_mark()?;
+                    self.stack.push(Item::Tuple(Rc::new(items)));
+                }
+                TUPLE1 | TUPLE2 | TUPLE3 => {
+                    let len = (opcode - TUPLE1 + 1) as usize;
+                    if self.stack.len() < len {
+                        return Err(self.error("stack underflow"));
+                    }
+                    let items = self.stack.split_off(self.stack.len() - len);
+                    self.stack.push(Item::Tuple(Rc::new(items)));
+                }
+                EMPTY_DICT => self.stack.push(Item::Dict(Default::default())),
+                DICT => {
+                    let items = self.pop_mark()?;
+                    self.stack.push(Item::Dict(Default::default()));
+                    self.set_items(items)?;
+                }
+                SETITEM => {
+                    let value = self.pop()?;
+                    let key = self.pop()?;
+                    self.set_items(vec![key, value])?;
+                }
+                SETITEMS => {
+                    let items = self.pop_mark()?;
+                    self.set_items(items)?;
+                }
+                GLOBAL => {
+                    let module = self.read_line()?;
+                    let name = self.read_line()?;
+                    self.stack.push(Item::Global(format!("{module}.{name}")));
+                }
+                STACK_GLOBAL => {
+                    let name = self.pop()?.into_value();
+                    let module = self.pop()?.into_value();
+                    match (module.as_str(), name.as_str()) {
+                        (Some(module), Some(name)) => {
+                            self.stack.push(Item::Global(format!("{module}.{name}")))
+                        }
+                        _ => return Err(self.error("invalid global")),
+                    }
+                }
+                REDUCE | NEWOBJ => {
+                    let args = self.pop()?;
+                    self.reduce(args)?;
+                }
+                NEWOBJ_EX => {
+                    let _kwargs = self.pop()?;
+                    let args = self.pop()?;
+                    self.reduce(args)?;
+                }
+                BUILD => {
+                    let state = self.pop()?;
+                    match self.top()? {
+                        Item::Object(object) => object.borrow_mut().state = Some(state),
+                        _ => return Err(self.error("build on non-object")),
+                    }
+                }
+                PUT => {
+                    let index = self
+                        .read_line()?
+                        .parse::<usize>()
+                        .map_err(|_| self.error("invalid memo index"))?;
+                    self.memo_put(index)?;
+                }
+                BINPUT => {
+                    let index = self.read_u8()? as usize;
+                    self.memo_put(index)?;
+                }
+                LONG_BINPUT => {
+                    let index = self.read_u32()?;
+                    self.memo_put(index)?;
+                }
+                MEMOIZE => self.memo_put(self.memo.len())?,
+                GET => {
+                    let index = self
+                        .read_line()?
+                        .parse::<usize>()
+                        .map_err(|_| self.error("invalid memo index"))?;
+                    self.memo_get(index)?;
+                }
+                BINGET => {
+                    let index = self.read_u8()? as usize;
+                    self.memo_get(index)?;
+                }
+                LONG_BINGET => {
+                    let index = self.read_u32()?;
+                    self.memo_get(index)?;
+                }
+                opcode => return Err(self.error(format!("unsupported opcode {opcode:#04x}"))),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip() {
+        let value = Value::Tuple(vec![
+            Value::Int(-1),
+            Value::Str("_NewWindow".into()),
+            Value::Tuple(vec![Value::Str("Veusz \u{03c7}²".into())]),
+            Value::Dict(vec![(Value::Str("hidden".into()), Value::Bool(false))]),
+            Value::List(vec![
+                Value::None,
+                Value::Float(1.5),
+                Value::Int(i64::MIN),
+                Value::Bytes(vec![0, 1, 2]),
+            ]),
+        ]);
+        assert_eq!(value, loads(&dumps(&value)).unwrap());
+    }
+
+    #[test]
+    fn array_is_sent_as_frombuffer() {
+        let value = loads(&dumps(&Value::Array(vec![1.0, -2.5]))).unwrap();
+        let Value::Object { class, args, .. } = value else {
+            panic!("not an object: {value:?}");
+        };
+        assert_eq!("numpy.frombuffer", class);
+        let mut bytes = 1.0f64.to_le_bytes().to_vec();
+        bytes.extend_from_slice(&(-2.5f64).to_le_bytes());
+        assert_eq!(
+            Value::Tuple(vec![Value::Bytes(bytes), Value::Str("<f8".into())]),
+            *args
+        );
+    }
+
+    #[test]
+    fn python_protocol_4_exception() {
+        // pickle.dumps(ValueError('x'), protocol=4)
+        let data = b"\x80\x04\x95\x23\x00\x00\x00\x00\x00\x00\x00\x8c\x08builtins\x94\x8c\nValueError\x94\x93\x94\x8c\x01x\x94\x85\x94R\x94.";
+        assert_eq!(
+            Value::Object {
+                class: "builtins.ValueError".into(),
+                args: Box::new(Value::Tuple(vec![Value::Str("x".into())])),
+                state: None,
+            },
+            loads(data).unwrap()
+        );
+    }
+
+    #[test]
+    fn python_protocol_2_memoized_containers() {
+        // l = ['a', 1]; pickle.dumps((l, {'k': l}), protocol=2)
+        let data = b"\x80\x02]q\x00(X\x01\x00\x00\x00aq\x01K\x01e}q\x02X\x01\x00\x00\x00kq\x03h\x00s\x86q\x04.";
+        let list = Value::List(vec![Value::Str("a".into()), Value::Int(1)]);
+        assert_eq!(
+            Value::Tuple(vec![
+                list.clone(),
+                Value::Dict(vec![(Value::Str("k".into()), list)])
+            ]),
+            loads(data).unwrap()
+        );
+    }
+
+    #[test]
+    fn numpy_array_is_converted() {
+        let dtype = Value::Object {
+            class: "numpy.dtype".into(),
+            args: Box::new(Value::Tuple(vec![
+                Value::Str("f8".into()),
+                Value::Bool(false),
+                Value::Bool(true),
+            ])),
+            state: Some(Box::new(Value::Tuple(vec![
+                Value::Int(3),
+                Value::Str("<".into()),
+            ]))),
+        };
+        let mut bytes = Vec::new();
+        for value in [1.0f64, 2.0, 3.0, 4.0] {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        let array = |shape: Vec<Value>| Value::Object {
+            class: "numpy.core.multiarray._reconstruct".into(),
+            args: Box::new(Value::Tuple(vec![])),
+            state: Some(Box::new(Value::Tuple(vec![
+                Value::Int(1),
+                Value::Tuple(shape),
+                dtype.clone(),
+                Value::Bool(false),
+                Value::Bytes(bytes.clone()),
+            ]))),
+        };
+
+        assert_eq!(
+            Value::Array(vec![1.0, 2.0, 3.0, 4.0]),
+            loads(&dumps(&array(vec![Value::Int(4)]))).unwrap()
+        );
+        assert_eq!(
+            Value::List(vec![
+                Value::Array(vec![1.0, 2.0]),
+                Value::Array(vec![3.0, 4.0])
+            ]),
+            loads(&dumps(&array(vec![Value::Int(2), Value::Int(2)]))).unwrap()
+        );
+    }
+}
@@ -0,0 +1,75 @@
+use std::ffi::OsStr;
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+use std::process::ExitStatus;
+use std::time::Duration;
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[derive(Debug)]
+pub enum Error {
+    /// The veusz executable could not be found (not installed or not in `PATH`)
+    BinaryNotFound(String),
+    /// The veusz executable was found but could not be started
+    Spawn(std::io::Error),
+    /// Reading or writing a file or a pipe of the veusz process failed
+    Io(std::io::Error),
+    /// The veusz process exited unsuccessfully, with its output if it was captured
+    NonZeroExit(ExitStatus, Option<String>),
+    /// The veusz process did not exit in time and was killed
+    Timeout(Duration),
+    /// The veusz process did not behave as expected by the protocol
+    Protocol(String),
+    /// veusz reported an error while executing a command
+    Command(String),
+    /// veusz exited successfully but did not produce the export target
+    MissingExport(PathBuf),
+    /// The document is inconsistent, for example because of datasets of mismatching lengths
+    Invalid(String),
+}
+
+impl Error {
+    pub(crate) fn spawn(program: &OsStr, error: std::io::Error) -> Self {
+        if error.kind() == std::io::ErrorKind::NotFound {
+            Error::BinaryNotFound(program.to_string_lossy().into_owned())
+        } else {
+            Error::Spawn(error)
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::BinaryNotFound(program) => write!(f, "veusz executable not found: {program}"),
+            Error::Spawn(e) => write!(f, "failed to start veusz: {e}"),
+            Error::Io(e) => write!(f, "i/o error: {e}"),
+            Error::NonZeroExit(status, None) => write!(f, "veusz exited unsuccessfully: {status}"),
+            Error::NonZeroExit(status, Some(output)) => {
+                write!(f, "veusz exited unsuccessfully: {status}\n{output}")
+            }
+            Error::Timeout(timeout) => write!(f, "veusz did not exit within {timeout:?}"),
+            Error::Protocol(message) => write!(f, "protocol error: {message}"),
+            Error::Command(message) => write!(f, "veusz command failed: {message}"),
+            Error::MissingExport(path) => {
+                write!(f, "veusz did not produce the export {}", path.display())
+            }
+            Error::Invalid(message) => write!(f, "invalid document: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Spawn(e) | Error::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Self {
+        Error::Io(error)
+    }
+}
@@ -1,58 +1,297 @@
-use crate::api1::AsVueszApi1ValueStr;
-use crate::CommandLineEmbeddingInterface;
+use crate::api1::{AsVueszApi1ValueStr, PyList, PyStr};
+use crate::style::ColorName;
+use crate::{CommandLineEmbeddingInterface, Error, Result};
+use std::fmt::Display;
 use std::io::Write;
+use std::path::Path;
 
 #[derive(derive_more::From)]
 pub enum Export {
     Svg(SvgExport),
+    Pdf(PdfExport),
+    Eps(EpsExport),
+    Ps(PsExport),
+    Png(PngExport),
+    Jpeg(JpegExport),
+    Tiff(TiffExport),
+    Bmp(BmpExport),
+    Emf(EmfExport),
 }
 
-impl CommandLineEmbeddingInterface for Export {
-    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+impl Export {
+    /// Picks the export format by the extension of the given filename, returns [`None`]
+    /// for unknown or missing extensions.
+    pub fn target(filename: impl Into<String>) -> Option<Self> {
+        let filename = filename.into();
+        let extension = Path::new(&filename)
+            .extension()?
+            .to_str()?
+            .to_ascii_lowercase();
+        Some(match extension.as_str() {
+            "svg" => SvgExport::target(filename).into(),
+            "pdf" => PdfExport::target(filename).into(),
+            "eps" => EpsExport::target(filename).into(),
+            "ps" => PsExport::target(filename).into(),
+            "png" => PngExport::target(filename).into(),
+            "jpg" | "jpeg" => JpegExport::target(filename).into(),
+            "tiff" | "tif" => TiffExport::target(filename).into(),
+            "bmp" => BmpExport::target(filename).into(),
+            "emf" => EmfExport::target(filename).into(),
+            _ => return None,
+        })
+    }
+
+    pub fn filename(&self) -> &str {
         match self {
-            Export::Svg(svg) => svg.write(writer),
+            Export::Svg(export) => export.filename(),
+            Export::Pdf(export) => export.filename(),
+            Export::Eps(export) => export.filename(),
+            Export::Ps(export) => export.filename(),
+            Export::Png(export) => export.filename(),
+            Export::Jpeg(export) => export.filename(),
+            Export::Tiff(export) => export.filename(),
+            Export::Bmp(export) => export.filename(),
+            Export::Emf(export) => export.filename(),
         }
     }
 }
 
-pub struct SvgExport {
+impl Export {
+    /// The keyword arguments of the export, like `dpi=300`, as passed to
+    /// `--export-option` on the command line.
+    pub(crate) fn options(&self) -> Vec<String> {
+        self.call().options
+    }
+
+    fn call(&self) -> ExportCall {
+        match self {
+            Export::Svg(export) => export.call(),
+            Export::Pdf(export) => export.call(),
+            Export::Eps(export) => export.call(),
+            Export::Ps(export) => export.call(),
+            Export::Png(export) => export.call(),
+            Export::Jpeg(export) => export.call(),
+            Export::Tiff(export) => export.call(),
+            Export::Bmp(export) => export.call(),
+            Export::Emf(export) => export.call(),
+        }
+    }
+}
+
+impl CommandLineEmbeddingInterface for Export {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        self.call().write(writer)
+    }
+}
+
+/// Collects the parameters of an `Export(...)` call.
+struct ExportCall {
     filename: String,
-    color: Option<bool>,
-    page: Option<usize>,
-    text_as_text: Option<bool>,
+    options: Vec<String>,
 }
 
-impl SvgExport {
-    pub fn target(filename: impl Into<String>) -> Self {
-        let mut filename = filename.into();
+impl ExportCall {
+    fn new(filename: &str) -> Self {
+        Self {
+            filename: filename.to_string(),
+            options: Vec::new(),
+        }
+    }
 
-        if !filename.ends_with(".svg") {
-            filename.push_str(".svg");
+    fn bool(mut self, name: &str, value: Option<bool>) -> Self {
+        if let Some(value) = value {
+            self.options
+                .push(format!("{name}={}", value.as_veusz_api1_value_str()));
         }
+        self
+    }
 
-        SvgExport {
-            filename,
-            color: None,
-            page: None,
-            text_as_text: None,
+    fn raw(mut self, name: &str, value: Option<impl Display>) -> Self {
+        if let Some(value) = value {
+            self.options.push(format!("{name}={value}"));
         }
+        self
     }
 
-    pub fn set_color(&mut self, color: bool) {
-        self.color = Some(color);
+    fn str(mut self, name: &str, value: Option<&str>) -> Self {
+        if let Some(value) = value {
+            self.options.push(format!("{name}={}", PyStr(value)));
+        }
+        self
     }
 
-    pub fn with_color(mut self, color: bool) -> Self {
-        self.set_color(color);
+    fn pages(mut self, pages: &[usize]) -> Self {
+        match pages {
+            [] => {}
+            [page] => self.options.push(format!("page={page}")),
+            pages => self.options.push(format!("page={}", PyList(pages))),
+        }
         self
     }
 
-    pub fn set_page(&mut self, page: usize) {
-        self.page = Some(page);
+    fn write<W: Write>(self, writer: &mut W) -> std::io::Result<()> {
+        write!(writer, "Export({}", PyStr(&self.filename))?;
+        for option in &self.options {
+            write!(writer, ", {option}")?;
+        }
+        writeln!(writer, ")")
+    }
+}
+
+/// Appends the extension unless the filename already ends with one of the given ones.
+fn with_extension(filename: impl Into<String>, extensions: &[&str]) -> String {
+    let mut filename = filename.into();
+    let has_extension = Path::new(&filename)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            extensions
+                .iter()
+                .any(|candidate| extension.eq_ignore_ascii_case(candidate))
+        });
+
+    if !has_extension {
+        filename.push('.');
+        filename.push_str(extensions[0]);
+    }
+
+    filename
+}
+
+/// The filename, colour and pages every export format has.
+struct ExportOptions {
+    filename: String,
+    color: Option<bool>,
+    pages: Vec<usize>,
+}
+
+impl ExportOptions {
+    fn new(filename: impl Into<String>, extensions: &[&str]) -> Self {
+        Self {
+            filename: with_extension(filename, extensions),
+            color: None,
+            pages: Vec::new(),
+        }
+    }
+
+    fn call(&self) -> ExportCall {
+        ExportCall::new(&self.filename)
+            .bool("color", self.color)
+            .pages(&self.pages)
+    }
+}
+
+/// Antialiasing and background of the bitmap formats.
+#[derive(Default)]
+struct BitmapOptions {
+    antialias: Option<bool>,
+    backcolor: Option<String>,
+}
+
+impl BitmapOptions {
+    fn append_to(&self, call: ExportCall) -> ExportCall {
+        call.bool("antialias", self.antialias)
+            .str("backcolor", self.backcolor.as_deref())
+    }
+}
+
+/// Implements the setters of the [`ExportOptions`] in the `options` field.
+macro_rules! export_options {
+    ($export:ty) => {
+        impl $export {
+            pub fn filename(&self) -> &str {
+                &self.options.filename
+            }
+
+            pub fn set_color(&mut self, color: bool) {
+                self.options.color = Some(color);
+            }
+
+            pub fn with_color(mut self, color: bool) -> Self {
+                self.set_color(color);
+                self
+            }
+
+            pub fn set_page(&mut self, page: usize) {
+                self.options.pages = vec![page];
+            }
+
+            pub fn with_page(mut self, page: usize) -> Self {
+                self.set_page(page);
+                self
+            }
+
+            pub fn set_pages(&mut self, pages: impl IntoIterator<Item = usize>) {
+                self.options.pages = pages.into_iter().collect();
+            }
+
+            pub fn with_pages(mut self, pages: impl IntoIterator<Item = usize>) -> Self {
+                self.set_pages(pages);
+                self
+            }
+        }
+    };
+}
+
+/// Implements the setters of the [`BitmapOptions`] in the `bitmap` field.
+macro_rules! bitmap_options {
+    ($export:ty) => {
+        impl $export {
+            pub fn set_antialias(&mut self, antialias: bool) {
+                self.bitmap.antialias = Some(antialias);
+            }
+
+            pub fn with_antialias(mut self, antialias: bool) -> Self {
+                self.set_antialias(antialias);
+                self
+            }
+
+            /// Color like `#ffffff00` (transparent white) to fill the background with
+            pub fn set_backcolor(&mut self, color: impl Into<String>) {
+                self.bitmap.backcolor = Some(color.into());
+            }
+
+            pub fn with_backcolor(mut self, color: impl Into<String>) -> Self {
+                self.set_backcolor(color);
+                self
+            }
+
+            pub fn set_backcolor_by_name(&mut self, color_name: ColorName) {
+                self.bitmap.backcolor = Some(color_name.as_veusz_api1_value_str().to_string());
+            }
+
+            pub fn with_backcolor_by_name(mut self, color_name: ColorName) -> Self {
+                self.set_backcolor_by_name(color_name);
+                self
+            }
+        }
+    };
+}
+
+/// Scalable Vector Graphics
+pub struct SvgExport {
+    options: ExportOptions,
+    svgdpi: Option<u32>,
+    text_as_text: Option<bool>,
+}
+
+export_options!(SvgExport);
+
+impl SvgExport {
+    pub fn target(filename: impl Into<String>) -> Self {
+        SvgExport {
+            options: ExportOptions::new(filename, &["svg"]),
+            svgdpi: None,
+            text_as_text: None,
+        }
+    }
+
+    pub fn set_dpi(&mut self, dpi: u32) {
+        self.svgdpi = Some(dpi);
     }
 
-    pub fn with_page(mut self, page: usize) -> Self {
-        self.set_page(page);
+    pub fn with_dpi(mut self, dpi: u32) -> Self {
+        self.set_dpi(dpi);
         self
     }
 
@@ -64,29 +303,462 @@ impl SvgExport {
         self.set_text_as_text(text_as_text);
         self
     }
+
+    fn call(&self) -> ExportCall {
+        self.options
+            .call()
+            .raw("svgdpi", self.svgdpi)
+            .bool("svgtextastext", self.text_as_text)
+    }
 }
 
 impl CommandLineEmbeddingInterface for SvgExport {
     fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        let mut parameters = Vec::with_capacity(4);
+        self.call().write(writer)
+    }
+}
+
+/// Portable Document Format, multiple pages are exported into a single document
+pub struct PdfExport {
+    options: ExportOptions,
+    pdfdpi: Option<u32>,
+}
 
-        parameters.push(format!("'{}'", self.filename));
+export_options!(PdfExport);
 
-        if let Some(color) = self.color {
-            parameters.push(format!("color={}", color.as_veusz_api1_value_str()));
+impl PdfExport {
+    pub fn target(filename: impl Into<String>) -> Self {
+        PdfExport {
+            options: ExportOptions::new(filename, &["pdf"]),
+            pdfdpi: None,
         }
+    }
 
-        if let Some(page) = self.page {
-            parameters.push(format!("page={page}"));
+    pub fn set_dpi(&mut self, dpi: u32) {
+        self.pdfdpi = Some(dpi);
+    }
+
+    pub fn with_dpi(mut self, dpi: u32) -> Self {
+        self.set_dpi(dpi);
+        self
+    }
+
+    fn call(&self) -> ExportCall {
+        self.options.call().raw("pdfdpi", self.pdfdpi)
+    }
+}
+
+impl CommandLineEmbeddingInterface for PdfExport {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        self.call().write(writer)
+    }
+}
+
+/// Encapsulated PostScript
+pub struct EpsExport {
+    options: ExportOptions,
+    pdfdpi: Option<u32>,
+}
+
+export_options!(EpsExport);
+
+impl EpsExport {
+    pub fn target(filename: impl Into<String>) -> Self {
+        EpsExport {
+            options: ExportOptions::new(filename, &["eps"]),
+            pdfdpi: None,
         }
+    }
+
+    pub fn set_dpi(&mut self, dpi: u32) {
+        self.pdfdpi = Some(dpi);
+    }
+
+    pub fn with_dpi(mut self, dpi: u32) -> Self {
+        self.set_dpi(dpi);
+        self
+    }
+
+    fn call(&self) -> ExportCall {
+        self.options.call().raw("pdfdpi", self.pdfdpi)
+    }
+}
+
+impl CommandLineEmbeddingInterface for EpsExport {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        self.call().write(writer)
+    }
+}
+
+/// PostScript
+pub struct PsExport {
+    options: ExportOptions,
+    pdfdpi: Option<u32>,
+}
+
+export_options!(PsExport);
 
-        if let Some(text_as_text) = self.text_as_text {
-            parameters.push(format!(
-                "svgtextastext={}",
-                text_as_text.as_veusz_api1_value_str()
-            ));
+impl PsExport {
+    pub fn target(filename: impl Into<String>) -> Self {
+        PsExport {
+            options: ExportOptions::new(filename, &["ps"]),
+            pdfdpi: None,
+        }
+    }
+
+    pub fn set_dpi(&mut self, dpi: u32) {
+        self.pdfdpi = Some(dpi);
+    }
+
+    pub fn with_dpi(mut self, dpi: u32) -> Self {
+        self.set_dpi(dpi);
+        self
+    }
+
+    fn call(&self) -> ExportCall {
+        self.options.call().raw("pdfdpi", self.pdfdpi)
+    }
+}
+
+impl CommandLineEmbeddingInterface for PsExport {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        self.call().write(writer)
+    }
+}
+
+/// Portable Network Graphics
+pub struct PngExport {
+    options: ExportOptions,
+    bitmap: BitmapOptions,
+    dpi: Option<u32>,
+}
+
+export_options!(PngExport);
+bitmap_options!(PngExport);
+
+impl PngExport {
+    pub fn target(filename: impl Into<String>) -> Self {
+        PngExport {
+            options: ExportOptions::new(filename, &["png"]),
+            bitmap: BitmapOptions::default(),
+            dpi: None,
         }
+    }
+
+    pub fn set_dpi(&mut self, dpi: u32) {
+        self.dpi = Some(dpi);
+    }
+
+    pub fn with_dpi(mut self, dpi: u32) -> Self {
+        self.set_dpi(dpi);
+        self
+    }
+
+    fn call(&self) -> ExportCall {
+        let call = self.options.call().raw("dpi", self.dpi);
+        self.bitmap.append_to(call)
+    }
+}
+
+impl CommandLineEmbeddingInterface for PngExport {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        self.call().write(writer)
+    }
+}
+
+/// JPEG
+pub struct JpegExport {
+    options: ExportOptions,
+    bitmap: BitmapOptions,
+    dpi: Option<u32>,
+    quality: Option<u8>,
+}
+
+export_options!(JpegExport);
+bitmap_options!(JpegExport);
+
+impl JpegExport {
+    pub fn target(filename: impl Into<String>) -> Self {
+        JpegExport {
+            options: ExportOptions::new(filename, &["jpg", "jpeg"]),
+            bitmap: BitmapOptions::default(),
+            dpi: None,
+            quality: None,
+        }
+    }
+
+    pub fn set_dpi(&mut self, dpi: u32) {
+        self.dpi = Some(dpi);
+    }
+
+    pub fn with_dpi(mut self, dpi: u32) -> Self {
+        self.set_dpi(dpi);
+        self
+    }
+
+    /// Quality from 0 to 100, larger values are rejected with [`Error::Invalid`].
+    pub fn set_quality(&mut self, quality: u8) -> Result<()> {
+        if quality > 100 {
+            return Err(Error::Invalid(format!(
+                "jpeg quality must be at most 100, got {quality}"
+            )));
+        }
+        self.quality = Some(quality);
+        Ok(())
+    }
+
+    /// Quality from 0 to 100, larger values are clamped to 100.
+    pub fn with_quality(mut self, quality: u8) -> Self {
+        self.quality = Some(quality.min(100));
+        self
+    }
+
+    fn call(&self) -> ExportCall {
+        let call = self
+            .options
+            .call()
+            .raw("dpi", self.dpi)
+            .raw("quality", self.quality);
+        self.bitmap.append_to(call)
+    }
+}
+
+impl CommandLineEmbeddingInterface for JpegExport {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        self.call().write(writer)
+    }
+}
+
+/// Tagged Image File Format
+pub struct TiffExport {
+    options: ExportOptions,
+    bitmap: BitmapOptions,
+    dpi: Option<u32>,
+}
+
+export_options!(TiffExport);
+bitmap_options!(TiffExport);
+
+impl TiffExport {
+    pub fn target(filename: impl Into<String>) -> Self {
+        TiffExport {
+            options: ExportOptions::new(filename, &["tiff", "tif"]),
+            bitmap: BitmapOptions::default(),
+            dpi: None,
+        }
+    }
+
+    pub fn set_dpi(&mut self, dpi: u32) {
+        self.dpi = Some(dpi);
+    }
+
+    pub fn with_dpi(mut self, dpi: u32) -> Self {
+        self.set_dpi(dpi);
+        self
+    }
+
+    fn call(&self) -> ExportCall {
+        let call = self.options.call().raw("dpi", self.dpi);
+        self.bitmap.append_to(call)
+    }
+}
+
+impl CommandLineEmbeddingInterface for TiffExport {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        self.call().write(writer)
+    }
+}
+
+/// Windows Bitmap
+pub struct BmpExport {
+    options: ExportOptions,
+    bitmap: BitmapOptions,
+    dpi: Option<u32>,
+}
+
+export_options!(BmpExport);
+bitmap_options!(BmpExport);
+
+impl BmpExport {
+    pub fn target(filename: impl Into<String>) -> Self {
+        BmpExport {
+            options: ExportOptions::new(filename, &["bmp"]),
+            bitmap: BitmapOptions::default(),
+            dpi: None,
+        }
+    }
+
+    pub fn set_dpi(&mut self, dpi: u32) {
+        self.dpi = Some(dpi);
+    }
+
+    pub fn with_dpi(mut self, dpi: u32) -> Self {
+        self.set_dpi(dpi);
+        self
+    }
+
+    fn call(&self) -> ExportCall {
+        let call = self.options.call().raw("dpi", self.dpi);
+        self.bitmap.append_to(call)
+    }
+}
+
+impl CommandLineEmbeddingInterface for BmpExport {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        self.call().write(writer)
+    }
+}
+
+/// Enhanced Metafile, requires the `pyemf3` python module to be available to veusz
+pub struct EmfExport {
+    options: ExportOptions,
+}
+
+export_options!(EmfExport);
+
+impl EmfExport {
+    pub fn target(filename: impl Into<String>) -> Self {
+        EmfExport {
+            options: ExportOptions::new(filename, &["emf"]),
+        }
+    }
+
+    fn call(&self) -> ExportCall {
+        self.options.call()
+    }
+}
+
+impl CommandLineEmbeddingInterface for EmfExport {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        self.call().write(writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api1::written;
+
+    #[test]
+    fn target_dispatches_on_extension() {
+        assert!(matches!(Export::target("a.svg"), Some(Export::Svg(_))));
+        assert!(matches!(Export::target("a.pdf"), Some(Export::Pdf(_))));
+        assert!(matches!(Export::target("a.eps"), Some(Export::Eps(_))));
+        assert!(matches!(Export::target("a.ps"), Some(Export::Ps(_))));
+        assert!(matches!(Export::target("a.png"), Some(Export::Png(_))));
+        assert!(matches!(Export::target("a.jpg"), Some(Export::Jpeg(_))));
+        assert!(matches!(Export::target("a.jpeg"), Some(Export::Jpeg(_))));
+        assert!(matches!(Export::target("a.tif"), Some(Export::Tiff(_))));
+        assert!(matches!(Export::target("a.tiff"), Some(Export::Tiff(_))));
+        assert!(matches!(Export::target("a.bmp"), Some(Export::Bmp(_))));
+        assert!(matches!(Export::target("a.emf"), Some(Export::Emf(_))));
+    }
+
+    #[test]
+    fn target_ignores_case_of_extension() {
+        let export = Export::target("Plot.PNG").unwrap();
+        assert!(matches!(export, Export::Png(_)));
+        assert_eq!("Plot.PNG", export.filename());
+        assert!(matches!(Export::target("plot.JpEg"), Some(Export::Jpeg(_))));
+    }
+
+    #[test]
+    fn target_rejects_unknown_or_missing_extension() {
+        assert!(Export::target("plot.xyz").is_none());
+        assert!(Export::target("plot").is_none());
+    }
+
+    #[test]
+    fn appends_missing_extension() {
+        assert_eq!("plot.svg", SvgExport::target("plot").filename());
+        assert_eq!("plot.jpg", JpegExport::target("plot").filename());
+        assert_eq!("plot.TIF", TiffExport::target("plot.TIF").filename());
+    }
+
+    #[test]
+    fn single_and_multiple_pages() {
+        assert_eq!(
+            "Export(u'a.emf', page=2)\n",
+            written(&EmfExport::target("a.emf").with_page(2), "")
+        );
+        assert_eq!(
+            "Export(u'a.pdf', color=False, page=[0, 1, 3])\n",
+            written(
+                &PdfExport::target("a.pdf")
+                    .with_color(false)
+                    .with_pages([0, 1, 3]),
+                "",
+            )
+        );
+        assert_eq!(
+            "Export(u'a.pdf')\n",
+            written(&PdfExport::target("a.pdf").with_pages([]), "")
+        );
+    }
+
+    #[test]
+    fn vector_format_kwargs() {
+        assert_eq!(
+            "Export(u'a.svg', svgdpi=300, svgtextastext=True)\n",
+            written(
+                &SvgExport::target("a.svg")
+                    .with_dpi(300)
+                    .with_text_as_text(true),
+                "",
+            )
+        );
+        assert_eq!(
+            "Export(u'a.pdf', pdfdpi=150)\n",
+            written(&PdfExport::target("a.pdf").with_dpi(150), "")
+        );
+        assert_eq!(
+            "Export(u'a.eps', pdfdpi=72)\n",
+            written(&EpsExport::target("a.eps").with_dpi(72), "")
+        );
+        assert_eq!(
+            "Export(u'a.ps', pdfdpi=96)\n",
+            written(&PsExport::target("a.ps").with_dpi(96), "")
+        );
+    }
+
+    #[test]
+    fn bitmap_format_kwargs() {
+        assert_eq!(
+            "Export(u'a.png', dpi=200, antialias=False, backcolor=u'#ffffff00')\n",
+            written(
+                &PngExport::target("a.png")
+                    .with_dpi(200)
+                    .with_antialias(false)
+                    .with_backcolor("#ffffff00"),
+                "",
+            )
+        );
+        assert_eq!(
+            "Export(u'a.tiff', dpi=100)\n",
+            written(&TiffExport::target("a.tiff").with_dpi(100), "")
+        );
+        assert_eq!(
+            "Export(u'a.bmp', antialias=True)\n",
+            written(&BmpExport::target("a.bmp").with_antialias(true), "")
+        );
+    }
 
-        writeln!(writer, "Export({})", parameters.join(", "))
+    #[test]
+    fn jpeg_quality() {
+        assert_eq!(
+            "Export(u'a.jpg', dpi=90, quality=100)\n",
+            written(
+                &JpegExport::target("a.jpg").with_dpi(90).with_quality(100),
+                ""
+            )
+        );
+        assert_eq!(
+            "Export(u'a.jpg', quality=100)\n",
+            written(&JpegExport::target("a.jpg").with_quality(101), "")
+        );
+        assert!(matches!(
+            JpegExport::target("a.jpg").set_quality(101),
+            Err(Error::Invalid(_))
+        ));
     }
 }
@@ -0,0 +1,798 @@
+use crate::api1::{cmd, AsVueszApi1ValueStr, PyNumber, PyStr, PyStrList};
+use crate::CommandLineEmbeddingInterface;
+use std::fmt::Display;
+use std::io::Write;
+
+/// Datasets read by veusz from a file. Linked imports are saved as reference to the
+/// file instead of their values and can be reloaded with `ReloadData()`.
+#[derive(derive_more::From)]
+pub enum Import {
+    Csv(CsvImport),
+    Text(TextImport),
+    Text2D(Text2DImport),
+    Hdf5(Hdf5Import),
+    Npy(NpyImport),
+    Npz(NpzImport),
+}
+
+impl CommandLineEmbeddingInterface for Import {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        match self {
+            Import::Csv(import) => import.write(writer),
+            Import::Text(import) => import.write(writer),
+            Import::Text2D(import) => import.write(writer),
+            Import::Hdf5(import) => import.write(writer),
+            Import::Npy(import) => import.write(writer),
+            Import::Npz(import) => import.write(writer),
+        }
+    }
+}
+
+/// Collects the parameters of an `ImportFile...(...)` call.
+struct ImportCall(Vec<String>);
+
+impl ImportCall {
+    fn new(filename: &str) -> Self {
+        Self(vec![PyStr(filename).to_string()])
+    }
+
+    fn arg(mut self, value: impl Display) -> Self {
+        self.0.push(value.to_string());
+        self
+    }
+
+    fn bool(mut self, name: &str, value: Option<bool>) -> Self {
+        if let Some(value) = value {
+            self.0
+                .push(format!("{name}={}", value.as_veusz_api1_value_str()));
+        }
+        self
+    }
+
+    fn raw(mut self, name: &str, value: Option<impl Display>) -> Self {
+        if let Some(value) = value {
+            self.0.push(format!("{name}={value}"));
+        }
+        self
+    }
+
+    fn str(mut self, name: &str, value: Option<&str>) -> Self {
+        if let Some(value) = value {
+            self.0.push(format!("{name}={}", PyStr(value)));
+        }
+        self
+    }
+
+    fn range(self, name: &str, value: Option<(f64, f64)>) -> Self {
+        self.raw(
+            name,
+            value.map(|(min, max)| format!("({}, {})", PyNumber(min), PyNumber(max))),
+        )
+    }
+
+    fn dict(mut self, name: &str, entries: &[(String, String)]) -> Self {
+        if !entries.is_empty() {
+            let entries = entries
+                .iter()
+                .map(|(key, value)| format!("{}: {}", PyStr(key), PyStr(value)))
+                .collect::<Vec<_>>();
+            self.0.push(format!("{name}={{{}}}", entries.join(", ")));
+        }
+        self
+    }
+
+    fn write<W: Write>(self, command: &str, writer: &mut W) -> std::io::Result<()> {
+        cmd::Call(command, &self.0).write(writer)
+    }
+}
+
+/// How the header of a CSV file names the datasets.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum HeaderMode {
+    /// Every column may contain several datasets, each starting with a header
+    Multi,
+    /// Only the first row contains the dataset names
+    FirstRow,
+    /// Datasets are named by their column, like `col1`
+    None,
+}
+
+impl AsVueszApi1ValueStr for HeaderMode {
+    fn as_veusz_api1_value_str(&self) -> &str {
+        match self {
+            HeaderMode::Multi => "multi",
+            HeaderMode::FirstRow => "1st",
+            HeaderMode::None => "none",
+        }
+    }
+}
+
+/// `ImportFileCSV`, datasets are named by the column headers.
+pub struct CsvImport {
+    filename: String,
+    delimiter: Option<String>,
+    text_delimiter: Option<String>,
+    header_mode: Option<HeaderMode>,
+    header_ignore: Option<usize>,
+    rows_ignore: Option<usize>,
+    read_rows: Option<bool>,
+    prefix: Option<String>,
+    suffix: Option<String>,
+    encoding: Option<String>,
+    linked: Option<bool>,
+    renames: Vec<(String, String)>,
+}
+
+impl CsvImport {
+    pub fn file(filename: impl Into<String>) -> Self {
+        Self {
+            filename: filename.into(),
+            delimiter: None,
+            text_delimiter: None,
+            header_mode: None,
+            header_ignore: None,
+            rows_ignore: None,
+            read_rows: None,
+            prefix: None,
+            suffix: None,
+            encoding: None,
+            linked: None,
+            renames: Vec::new(),
+        }
+    }
+
+    pub fn filename(&self) -> &str {
+        &self.filename
+    }
+
+    /// Defaults to `,`.
+    pub fn set_delimiter(&mut self, delimiter: impl Into<String>) {
+        self.delimiter = Some(delimiter.into());
+    }
+
+    pub fn with_delimiter(mut self, delimiter: impl Into<String>) -> Self {
+        self.set_delimiter(delimiter);
+        self
+    }
+
+    /// Defaults to `"`.
+    pub fn set_text_delimiter(&mut self, delimiter: impl Into<String>) {
+        self.text_delimiter = Some(delimiter.into());
+    }
+
+    pub fn with_text_delimiter(mut self, delimiter: impl Into<String>) -> Self {
+        self.set_text_delimiter(delimiter);
+        self
+    }
+
+    pub fn set_header_mode(&mut self, mode: HeaderMode) {
+        self.header_mode = Some(mode);
+    }
+
+    pub fn with_header_mode(mut self, mode: HeaderMode) -> Self {
+        self.set_header_mode(mode);
+        self
+    }
+
+    /// Number of lines to skip after each header.
+    pub fn set_header_ignore(&mut self, lines: usize) {
+        self.header_ignore = Some(lines);
+    }
+
+    pub fn with_header_ignore(mut self, lines: usize) -> Self {
+        self.set_header_ignore(lines);
+        self
+    }
+
+    /// Number of lines to skip at the start of the file.
+    pub fn set_rows_ignore(&mut self, lines: usize) {
+        self.rows_ignore = Some(lines);
+    }
+
+    pub fn with_rows_ignore(mut self, lines: usize) -> Self {
+        self.set_rows_ignore(lines);
+        self
+    }
+
+    /// Read datasets from rows instead of columns.
+    pub fn set_read_rows(&mut self, read_rows: bool) {
+        self.read_rows = Some(read_rows);
+    }
+
+    pub fn with_read_rows(mut self, read_rows: bool) -> Self {
+        self.set_read_rows(read_rows);
+        self
+    }
+
+    pub fn set_prefix(&mut self, prefix: impl Into<String>) {
+        self.prefix = Some(prefix.into());
+    }
+
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.set_prefix(prefix);
+        self
+    }
+
+    pub fn set_suffix(&mut self, suffix: impl Into<String>) {
+        self.suffix = Some(suffix.into());
+    }
+
+    pub fn with_suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.set_suffix(suffix);
+        self
+    }
+
+    /// Python codec name, defaults to `utf_8`.
+    pub fn set_encoding(&mut self, encoding: impl Into<String>) {
+        self.encoding = Some(encoding.into());
+    }
+
+    pub fn with_encoding(mut self, encoding: impl Into<String>) -> Self {
+        self.set_encoding(encoding);
+        self
+    }
+
+    pub fn set_linked(&mut self, linked: bool) {
+        self.linked = Some(linked);
+    }
+
+    pub fn with_linked(mut self, linked: bool) -> Self {
+        self.set_linked(linked);
+        self
+    }
+
+    /// Imports the dataset named `from` in the file as `to`.
+    pub fn add_rename(&mut self, from: impl Into<String>, to: impl Into<String>) {
+        self.renames.push((from.into(), to.into()));
+    }
+
+    pub fn with_rename(mut self, from: impl Into<String>, to: impl Into<String>) -> Self {
+        self.add_rename(from, to);
+        self
+    }
+}
+
+impl CommandLineEmbeddingInterface for CsvImport {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        ImportCall::new(&self.filename)
+            .bool("readrows", self.read_rows)
+            .str("dsprefix", self.prefix.as_deref())
+            .str("dssuffix", self.suffix.as_deref())
+            .bool("linked", self.linked)
+            .str("encoding", self.encoding.as_deref())
+            .str("delimiter", self.delimiter.as_deref())
+            .str("textdelimiter", self.text_delimiter.as_deref())
+            .raw("headerignore", self.header_ignore)
+            .raw("rowsignore", self.rows_ignore)
+            .str(
+                "headermode",
+                self.header_mode
+                    .as_ref()
+                    .map(AsVueszApi1ValueStr::as_veusz_api1_value_str),
+            )
+            .dict("renames", &self.renames)
+            .write("ImportFileCSV", writer)
+    }
+}
+
+/// `ImportFile`, a text file with columns separated by whitespace, named and typed by a
+/// descriptor like `x,+- y,+,-` or `label[1:3](text)`.
+pub struct TextImport {
+    filename: String,
+    descriptor: String,
+    use_blocks: Option<bool>,
+    ignore_text: Option<bool>,
+    prefix: Option<String>,
+    suffix: Option<String>,
+    encoding: Option<String>,
+    linked: Option<bool>,
+    renames: Vec<(String, String)>,
+}
+
+impl TextImport {
+    /// An empty descriptor reads the dataset names from the file.
+    pub fn file(filename: impl Into<String>, descriptor: impl Into<String>) -> Self {
+        Self {
+            filename: filename.into(),
+            descriptor: descriptor.into(),
+            use_blocks: None,
+            ignore_text: None,
+            prefix: None,
+            suffix: None,
+            encoding: None,
+            linked: None,
+            renames: Vec::new(),
+        }
+    }
+
+    pub fn filename(&self) -> &str {
+        &self.filename
+    }
+
+    /// Blocks separated by blank lines are imported as separate datasets, suffixed by
+    /// `_1`, `_2` and so on.
+    pub fn set_use_blocks(&mut self, use_blocks: bool) {
+        self.use_blocks = Some(use_blocks);
+    }
+
+    pub fn with_use_blocks(mut self, use_blocks: bool) -> Self {
+        self.set_use_blocks(use_blocks);
+        self
+    }
+
+    /// Skip lines containing text instead of failing.
+    pub fn set_ignore_text(&mut self, ignore_text: bool) {
+        self.ignore_text = Some(ignore_text);
+    }
+
+    pub fn with_ignore_text(mut self, ignore_text: bool) -> Self {
+        self.set_ignore_text(ignore_text);
+        self
+    }
+
+    pub fn set_prefix(&mut self, prefix: impl Into<String>) {
+        self.prefix = Some(prefix.into());
+    }
+
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.set_prefix(prefix);
+        self
+    }
+
+    pub fn set_suffix(&mut self, suffix: impl Into<String>) {
+        self.suffix = Some(suffix.into());
+    }
+
+    pub fn with_suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.set_suffix(suffix);
+        self
+    }
+
+    /// Python codec name, defaults to `utf_8`.
+    pub fn set_encoding(&mut self, encoding: impl Into<String>) {
+        self.encoding = Some(encoding.into());
+    }
+
+    pub fn with_encoding(mut self, encoding: impl Into<String>) -> Self {
+        self.set_encoding(encoding);
+        self
+    }
+
+    pub fn set_linked(&mut self, linked: bool) {
+        self.linked = Some(linked);
+    }
+
+    pub fn with_linked(mut self, linked: bool) -> Self {
+        self.set_linked(linked);
+        self
+    }
+
+    /// Imports the dataset named `from` in the file as `to`.
+    pub fn add_rename(&mut self, from: impl Into<String>, to: impl Into<String>) {
+        self.renames.push((from.into(), to.into()));
+    }
+
+    pub fn with_rename(mut self, from: impl Into<String>, to: impl Into<String>) -> Self {
+        self.add_rename(from, to);
+        self
+    }
+}
+
+impl CommandLineEmbeddingInterface for TextImport {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        ImportCall::new(&self.filename)
+            .arg(PyStr(&self.descriptor))
+            .bool("useblocks", self.use_blocks)
+            .bool("linked", self.linked)
+            .str("prefix", self.prefix.as_deref())
+            .str("suffix", self.suffix.as_deref())
+            .bool("ignoretext", self.ignore_text)
+            .str("encoding", self.encoding.as_deref())
+            .dict("renames", &self.renames)
+            .write("ImportFile", writer)
+    }
+}
+
+/// `ImportFile2D`, a text file with a matrix of values for each dataset, separated by
+/// blank lines.
+pub struct Text2DImport {
+    filename: String,
+    dataset_names: Vec<String>,
+    x_range: Option<(f64, f64)>,
+    y_range: Option<(f64, f64)>,
+    invert_rows: Option<bool>,
+    invert_cols: Option<bool>,
+    transpose: Option<bool>,
+    prefix: Option<String>,
+    suffix: Option<String>,
+    encoding: Option<String>,
+    linked: Option<bool>,
+}
+
+impl Text2DImport {
+    pub fn file(
+        filename: impl Into<String>,
+        dataset_names: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        Self {
+            filename: filename.into(),
+            dataset_names: dataset_names.into_iter().map(Into::into).collect(),
+            x_range: None,
+            y_range: None,
+            invert_rows: None,
+            invert_cols: None,
+            transpose: None,
+            prefix: None,
+            suffix: None,
+            encoding: None,
+            linked: None,
+        }
+    }
+
+    pub fn filename(&self) -> &str {
+        &self.filename
+    }
+
+    pub fn set_x_range(&mut self, min: f64, max: f64) {
+        self.x_range = Some((min, max));
+    }
+
+    pub fn with_x_range(mut self, min: f64, max: f64) -> Self {
+        self.set_x_range(min, max);
+        self
+    }
+
+    pub fn set_y_range(&mut self, min: f64, max: f64) {
+        self.y_range = Some((min, max));
+    }
+
+    pub fn with_y_range(mut self, min: f64, max: f64) -> Self {
+        self.set_y_range(min, max);
+        self
+    }
+
+    pub fn set_invert_rows(&mut self, invert: bool) {
+        self.invert_rows = Some(invert);
+    }
+
+    pub fn with_invert_rows(mut self, invert: bool) -> Self {
+        self.set_invert_rows(invert);
+        self
+    }
+
+    pub fn set_invert_cols(&mut self, invert: bool) {
+        self.invert_cols = Some(invert);
+    }
+
+    pub fn with_invert_cols(mut self, invert: bool) -> Self {
+        self.set_invert_cols(invert);
+        self
+    }
+
+    pub fn set_transpose(&mut self, transpose: bool) {
+        self.transpose = Some(transpose);
+    }
+
+    pub fn with_transpose(mut self, transpose: bool) -> Self {
+        self.set_transpose(transpose);
+        self
+    }
+
+    pub fn set_prefix(&mut self, prefix: impl Into<String>) {
+        self.prefix = Some(prefix.into());
+    }
+
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.set_prefix(prefix);
+        self
+    }
+
+    pub fn set_suffix(&mut self, suffix: impl Into<String>) {
+        self.suffix = Some(suffix.into());
+    }
+
+    pub fn with_suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.set_suffix(suffix);
+        self
+    }
+
+    /// Python codec name, defaults to `utf_8`.
+    pub fn set_encoding(&mut self, encoding: impl Into<String>) {
+        self.encoding = Some(encoding.into());
+    }
+
+    pub fn with_encoding(mut self, encoding: impl Into<String>) -> Self {
+        self.set_encoding(encoding);
+        self
+    }
+
+    pub fn set_linked(&mut self, linked: bool) {
+        self.linked = Some(linked);
+    }
+
+    pub fn with_linked(mut self, linked: bool) -> Self {
+        self.set_linked(linked);
+        self
+    }
+}
+
+impl CommandLineEmbeddingInterface for Text2DImport {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        ImportCall::new(&self.filename)
+            .arg(PyStrList(&self.dataset_names))
+            .range("xrange", self.x_range)
+            .range("yrange", self.y_range)
+            .bool("invertrows", self.invert_rows)
+            .bool("invertcols", self.invert_cols)
+            .bool("transpose", self.transpose)
+            .str("prefix", self.prefix.as_deref())
+            .str("suffix", self.suffix.as_deref())
+            .bool("linked", self.linked)
+            .str("encoding", self.encoding.as_deref())
+            .write("ImportFile2D", writer)
+    }
+}
+
+/// `ImportFileHDF5`, datasets or whole groups given by their path in the file, like
+/// `/measurements/temperature`.
+pub struct Hdf5Import {
+    filename: String,
+    items: Vec<String>,
+    names: Vec<(String, String)>,
+    prefix: Option<String>,
+    suffix: Option<String>,
+    linked: Option<bool>,
+}
+
+impl Hdf5Import {
+    pub fn file(filename: impl Into<String>) -> Self {
+        Self {
+            filename: filename.into(),
+            items: Vec::new(),
+            names: Vec::new(),
+            prefix: None,
+            suffix: None,
+            linked: None,
+        }
+    }
+
+    pub fn filename(&self) -> &str {
+        &self.filename
+    }
+
+    /// Imports the dataset or all datasets of the group at the given path.
+    pub fn add_item(&mut self, path: impl Into<String>) {
+        self.items.push(path.into());
+    }
+
+    pub fn with_item(mut self, path: impl Into<String>) -> Self {
+        self.add_item(path);
+        self
+    }
+
+    pub fn with_items(mut self, paths: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.items.extend(paths.into_iter().map(Into::into));
+        self
+    }
+
+    /// Imports the dataset at the given path with the given name instead of the last
+    /// component of the path.
+    pub fn add_named_item(&mut self, path: impl Into<String>, name: impl Into<String>) {
+        let path = path.into();
+        self.items.push(path.clone());
+        self.names.push((path, name.into()));
+    }
+
+    pub fn with_named_item(mut self, path: impl Into<String>, name: impl Into<String>) -> Self {
+        self.add_named_item(path, name);
+        self
+    }
+
+    pub fn set_prefix(&mut self, prefix: impl Into<String>) {
+        self.prefix = Some(prefix.into());
+    }
+
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.set_prefix(prefix);
+        self
+    }
+
+    pub fn set_suffix(&mut self, suffix: impl Into<String>) {
+        self.suffix = Some(suffix.into());
+    }
+
+    pub fn with_suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.set_suffix(suffix);
+        self
+    }
+
+    pub fn set_linked(&mut self, linked: bool) {
+        self.linked = Some(linked);
+    }
+
+    pub fn with_linked(mut self, linked: bool) -> Self {
+        self.set_linked(linked);
+        self
+    }
+}
+
+impl CommandLineEmbeddingInterface for Hdf5Import {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        ImportCall::new(&self.filename)
+            .arg(PyStrList(&self.items))
+            .dict("namemap", &self.names)
+            .str("prefix", self.prefix.as_deref())
+            .str("suffix", self.suffix.as_deref())
+            .bool("linked", self.linked)
+            .write("ImportFileHDF5", writer)
+    }
+}
+
+/// `ImportFileNPY`, a single array saved by `numpy.save`.
+pub struct NpyImport {
+    filename: String,
+    dataset_name: String,
+    linked: Option<bool>,
+}
+
+impl NpyImport {
+    pub fn file(filename: impl Into<String>, dataset_name: impl Into<String>) -> Self {
+        Self {
+            filename: filename.into(),
+            dataset_name: dataset_name.into(),
+            linked: None,
+        }
+    }
+
+    pub fn filename(&self) -> &str {
+        &self.filename
+    }
+
+    pub fn set_linked(&mut self, linked: bool) {
+        self.linked = Some(linked);
+    }
+
+    pub fn with_linked(mut self, linked: bool) -> Self {
+        self.set_linked(linked);
+        self
+    }
+}
+
+impl CommandLineEmbeddingInterface for NpyImport {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        ImportCall::new(&self.filename)
+            .arg(PyStr(&self.dataset_name))
+            .bool("linked", self.linked)
+            .write("ImportFileNPY", writer)
+    }
+}
+
+/// `ImportFileNPZ`, all arrays saved by `numpy.savez`, named by their key.
+pub struct NpzImport {
+    filename: String,
+    prefix: Option<String>,
+    suffix: Option<String>,
+    linked: Option<bool>,
+}
+
+impl NpzImport {
+    pub fn file(filename: impl Into<String>) -> Self {
+        Self {
+            filename: filename.into(),
+            prefix: None,
+            suffix: None,
+            linked: None,
+        }
+    }
+
+    pub fn filename(&self) -> &str {
+        &self.filename
+    }
+
+    pub fn set_prefix(&mut self, prefix: impl Into<String>) {
+        self.prefix = Some(prefix.into());
+    }
+
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.set_prefix(prefix);
+        self
+    }
+
+    pub fn set_suffix(&mut self, suffix: impl Into<String>) {
+        self.suffix = Some(suffix.into());
+    }
+
+    pub fn with_suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.set_suffix(suffix);
+        self
+    }
+
+    pub fn set_linked(&mut self, linked: bool) {
+        self.linked = Some(linked);
+    }
+
+    pub fn with_linked(mut self, linked: bool) -> Self {
+        self.set_linked(linked);
+        self
+    }
+}
+
+impl CommandLineEmbeddingInterface for NpzImport {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        ImportCall::new(&self.filename)
+            .str("prefix", self.prefix.as_deref())
+            .str("suffix", self.suffix.as_deref())
+            .bool("linked", self.linked)
+            .write("ImportFileNPZ", writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api1::written;
+
+    #[test]
+    fn csv() {
+        assert_eq!(
+            "ImportFileCSV(u'data.csv')\n",
+            written(&CsvImport::file("data.csv"), "")
+        );
+        assert_eq!(
+            "ImportFileCSV(u'log.csv', dsprefix=u'run1_', linked=True, delimiter=u';', \
+             headermode=u'1st', renames={u'T': u'temperature'})\n",
+            written(
+                &CsvImport::file("log.csv")
+                    .with_delimiter(";")
+                    .with_header_mode(HeaderMode::FirstRow)
+                    .with_prefix("run1_")
+                    .with_linked(true)
+                    .with_rename("T", "temperature"),
+                "",
+            )
+        );
+    }
+
+    #[test]
+    fn text_and_2d() {
+        assert_eq!(
+            "ImportFile(u'data.dat', u'x,+- y', useblocks=True)\n",
+            written(
+                &TextImport::file("data.dat", "x,+- y").with_use_blocks(true),
+                ""
+            )
+        );
+        assert_eq!(
+            "ImportFile2D(u'z.dat', [u'a', u'b'], xrange=(0, 1.5), transpose=True)\n",
+            written(
+                &Text2DImport::file("z.dat", ["a", "b"])
+                    .with_x_range(0.0, 1.5)
+                    .with_transpose(true),
+                "",
+            )
+        );
+    }
+
+    #[test]
+    fn hdf5_and_numpy() {
+        assert_eq!(
+            "ImportFileHDF5(u'data.h5', [u'/raw', u'/fit/y'], namemap={u'/fit/y': u'yfit'}, \
+             linked=True)\n",
+            written(
+                &Hdf5Import::file("data.h5")
+                    .with_item("/raw")
+                    .with_named_item("/fit/y", "yfit")
+                    .with_linked(true),
+                "",
+            )
+        );
+        assert_eq!(
+            "ImportFileNPY(u'x.npy', u'x')\n",
+            written(&NpyImport::file("x.npy", "x"), "")
+        );
+        assert_eq!(
+            "ImportFileNPZ(u'all.npz', suffix=u'_2')\n",
+            written(&NpzImport::file("all.npz").with_suffix("_2"), "")
+        );
+    }
+}
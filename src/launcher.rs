@@ -0,0 +1,325 @@
+use crate::{Error, Result};
+use std::ffi::OsString;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdin, ChildStdout, Command, ExitStatus, Stdio};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// Name of the environment variable which overrides the default veusz executable.
+pub const VEUSZ_BIN_ENV: &str = "VEUSZ_BIN";
+
+const VEUSZ_BIN: &str = "veusz";
+
+/// What to do with the stdout or stderr output of the veusz process.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum Output {
+    /// Forward the output to the output of this process
+    #[default]
+    Inherit,
+    /// Discard the output
+    Discard,
+    /// Collect the output and attach it to the [`Error::NonZeroExit`] if veusz fails
+    Capture,
+}
+
+impl Output {
+    fn stdio(self) -> Stdio {
+        match self {
+            Output::Inherit => Stdio::inherit(),
+            Output::Discard => Stdio::null(),
+            Output::Capture => Stdio::piped(),
+        }
+    }
+}
+
+/// Describes how the veusz process is started.
+#[derive(Debug, Clone, Default)]
+pub struct Launcher {
+    executable: Option<PathBuf>,
+    args: Vec<OsString>,
+    working_directory: Option<PathBuf>,
+    envs: Vec<(OsString, OsString)>,
+    stdout: Output,
+    stderr: Output,
+    timeout: Option<Duration>,
+}
+
+impl Launcher {
+    /// Without an explicit executable, the one named by the `VEUSZ_BIN` environment
+    /// variable is started or otherwise `veusz` is looked up in the `PATH`.
+    pub fn set_executable(&mut self, executable: impl Into<PathBuf>) {
+        self.executable = Some(executable.into());
+    }
+
+    pub fn with_executable(mut self, executable: impl Into<PathBuf>) -> Self {
+        self.set_executable(executable);
+        self
+    }
+
+    /// Arguments passed before the ones selecting the mode (like `--listen`), for example
+    /// `-m veusz` when the executable is a python interpreter.
+    pub fn add_arg(&mut self, arg: impl Into<OsString>) {
+        self.args.push(arg.into());
+    }
+
+    pub fn with_arg(mut self, arg: impl Into<OsString>) -> Self {
+        self.add_arg(arg);
+        self
+    }
+
+    pub fn with_args(mut self, args: impl IntoIterator<Item = impl Into<OsString>>) -> Self {
+        self.args.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn set_working_directory(&mut self, directory: impl Into<PathBuf>) {
+        self.working_directory = Some(directory.into());
+    }
+
+    pub fn with_working_directory(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.set_working_directory(directory);
+        self
+    }
+
+    /// Environment variables like `QT_QPA_PLATFORM` or `PYTHONPATH`.
+    pub fn add_env(&mut self, key: impl Into<OsString>, value: impl Into<OsString>) {
+        self.envs.push((key.into(), value.into()));
+    }
+
+    pub fn with_env(mut self, key: impl Into<OsString>, value: impl Into<OsString>) -> Self {
+        self.add_env(key, value);
+        self
+    }
+
+    pub fn with_envs(
+        mut self,
+        envs: impl IntoIterator<Item = (impl Into<OsString>, impl Into<OsString>)>,
+    ) -> Self {
+        self.envs
+            .extend(envs.into_iter().map(|(k, v)| (k.into(), v.into())));
+        self
+    }
+
+    /// Ignored by interactive sessions, which read replies from stdout.
+    pub fn set_stdout(&mut self, stdout: Output) {
+        self.stdout = stdout;
+    }
+
+    pub fn with_stdout(mut self, stdout: Output) -> Self {
+        self.set_stdout(stdout);
+        self
+    }
+
+    pub fn set_stderr(&mut self, stderr: Output) {
+        self.stderr = stderr;
+    }
+
+    pub fn with_stderr(mut self, stderr: Output) -> Self {
+        self.set_stderr(stderr);
+        self
+    }
+
+    /// Maximum time to wait for veusz to exit before it is killed, and for the reply to
+    /// a query of a [`VeuszSession`](crate::session::VeuszSession).
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = Some(timeout);
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.set_timeout(timeout);
+        self
+    }
+
+    pub(crate) fn working_directory(&self) -> Option<&Path> {
+        self.working_directory.as_deref()
+    }
+
+    pub(crate) fn has_env(&self, key: &str) -> bool {
+        self.envs.iter().any(|(k, _)| k == key)
+    }
+
+    pub fn executable(&self) -> PathBuf {
+        self.executable
+            .clone()
+            .or_else(|| std::env::var_os(VEUSZ_BIN_ENV).map(PathBuf::from))
+            .unwrap_or_else(|| PathBuf::from(VEUSZ_BIN))
+    }
+
+    /// The command to start veusz with the given mode arguments and a piped stdin.
+    pub(crate) fn command(&self, mode: &[&str]) -> Command {
+        let mut command = Command::new(self.executable());
+        command
+            .args(&self.args)
+            .args(mode)
+            .envs(self.envs.iter().map(|(k, v)| (k, v)))
+            .stdin(Stdio::piped())
+            .stdout(self.stdout.stdio())
+            .stderr(self.stderr.stdio());
+        if let Some(directory) = &self.working_directory {
+            command.current_dir(directory);
+        }
+        command
+    }
+
+    pub(crate) fn spawn(&self, mut command: Command) -> Result<Process> {
+        let mut child = command
+            .spawn()
+            .map_err(|e| Error::spawn(command.get_program(), e))?;
+
+        Ok(Process {
+            stdout: capture(self.stdout, &mut child.stdout),
+            stderr: capture(self.stderr, &mut child.stderr),
+            child,
+            timeout: self.timeout,
+        })
+    }
+}
+
+fn capture(
+    policy: Output,
+    output: &mut Option<impl Read + Send + 'static>,
+) -> Option<JoinHandle<Vec<u8>>> {
+    if policy != Output::Capture {
+        return None;
+    }
+    output.take().map(|mut output| {
+        std::thread::spawn(move || {
+            let mut buffer = Vec::new();
+            let _ = output.read_to_end(&mut buffer);
+            buffer
+        })
+    })
+}
+
+/// A veusz process started by a [`Launcher`].
+pub(crate) struct Process {
+    child: Child,
+    timeout: Option<Duration>,
+    stdout: Option<JoinHandle<Vec<u8>>>,
+    stderr: Option<JoinHandle<Vec<u8>>>,
+}
+
+impl Process {
+    /// Wraps a process started by other means, without timeout and captured output.
+    pub(crate) fn from_child(child: Child) -> Self {
+        Self {
+            child,
+            timeout: None,
+            stdout: None,
+            stderr: None,
+        }
+    }
+
+    pub(crate) fn stdin(&mut self) -> Result<&mut ChildStdin> {
+        self.child
+            .stdin
+            .as_mut()
+            .ok_or_else(|| Error::Protocol("stdin of the veusz process is not piped".into()))
+    }
+
+    pub(crate) fn take_stdin(&mut self) -> Result<ChildStdin> {
+        self.child
+            .stdin
+            .take()
+            .ok_or_else(|| Error::Protocol("stdin of the veusz process is not piped".into()))
+    }
+
+    pub(crate) fn take_stdout(&mut self) -> Result<ChildStdout> {
+        self.child
+            .stdout
+            .take()
+            .ok_or_else(|| Error::Protocol("stdout of the veusz process is not piped".into()))
+    }
+
+    pub(crate) fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    pub(crate) fn try_wait(&mut self) -> Result<Option<ExitStatus>> {
+        Ok(self.child.try_wait()?)
+    }
+
+    /// Waits for the process to exit, kills it once the timeout elapsed and fails if it
+    /// exited unsuccessfully.
+    pub(crate) fn wait(&mut self) -> Result<ExitStatus> {
+        let status = match self.timeout {
+            None => self.child.wait()?,
+            Some(timeout) => {
+                let start = Instant::now();
+                loop {
+                    if let Some(status) = self.child.try_wait()? {
+                        break status;
+                    }
+                    if start.elapsed() >= timeout {
+                        self.kill();
+                        return Err(Error::Timeout(timeout));
+                    }
+                    std::thread::sleep(Duration::from_millis(10));
+                }
+            }
+        };
+
+        if status.success() {
+            return Ok(status);
+        }
+
+        let mut output = Vec::new();
+        for handle in [self.stdout.take(), self.stderr.take()]
+            .into_iter()
+            .flatten()
+        {
+            output.extend(handle.join().unwrap_or_default());
+        }
+        let output = (!output.is_empty()).then(|| String::from_utf8_lossy(&output).into_owned());
+        Err(Error::NonZeroExit(status, output))
+    }
+
+    /// Kills the process and reaps it, ignoring whether it already exited.
+    pub(crate) fn kill(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn sh(script: &str) -> Launcher {
+        Launcher::default()
+            .with_executable("sh")
+            .with_args(["-c", script])
+    }
+
+    #[test]
+    fn captured_output_is_attached_to_error() {
+        let launcher = sh("echo \"$GREETING\" >&2; exit 3")
+            .with_env("GREETING", "hello")
+            .with_stderr(Output::Capture);
+        let mut process = launcher.spawn(launcher.command(&[])).unwrap();
+        match process.wait() {
+            Err(Error::NonZeroExit(status, Some(output))) => {
+                assert_eq!(Some(3), status.code());
+                assert_eq!("hello\n", output);
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn timeout_kills_process() {
+        let launcher = sh("sleep 10").with_timeout(Duration::from_millis(50));
+        let mut process = launcher.spawn(launcher.command(&[])).unwrap();
+        assert!(matches!(process.wait(), Err(Error::Timeout(_))));
+    }
+
+    #[test]
+    fn missing_executable() {
+        let launcher = Launcher::default().with_executable("/nonexistent/veusz");
+        assert!(matches!(
+            launcher.spawn(launcher.command(&["--listen"])),
+            Err(Error::BinaryNotFound(_))
+        ));
+    }
+}
@@ -1,28 +1,54 @@
 use crate::api1::{cmd, CommandLineEmbeddingInterface};
-use crate::data::Data;
+use crate::data::Dataset;
 use crate::export::Export;
+use crate::import::Import;
+use crate::launcher::Launcher;
 use crate::page::Page;
+use crate::session::VeuszSession;
 use std::borrow::BorrowMut;
-use std::fs::OpenOptions;
+use std::ffi::OsString;
+use std::fs::{File, OpenOptions};
 use std::io::{BufWriter, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::ExitStatus;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+pub use error::{Error, Result};
 
 pub mod api1;
 pub mod data;
+pub mod embed;
+pub mod error;
 pub mod export;
+pub mod import;
+pub mod launcher;
 pub mod page;
+pub mod session;
 pub mod size;
 pub mod style;
+pub mod value;
 
 #[derive(Default)]
 pub struct Veusz {
-    data: Vec<Data>,
+    imports: Vec<Import>,
+    data: Vec<Dataset>,
     pages: Vec<Page>,
     exports: Vec<Export>,
+    launcher: Launcher,
 }
 
 impl Veusz {
+    /// Configures how the veusz process is started by [`Veusz::open`],
+    /// [`Veusz::open_saved_configuration`], [`Veusz::open_session`] and [`Veusz::render`].
+    pub fn set_launcher(&mut self, launcher: Launcher) {
+        self.launcher = launcher;
+    }
+
+    pub fn with_launcher(mut self, launcher: Launcher) -> Self {
+        self.set_launcher(launcher);
+        self
+    }
+
     pub fn add_page(&mut self, page: Page) {
         self.pages.push(page);
     }
@@ -37,17 +63,36 @@ impl Veusz {
         self
     }
 
-    pub fn add_data(&mut self, data: Data) {
-        self.data.push(data);
+    /// Imports are run before the datasets are set, so expressions may refer to
+    /// imported datasets.
+    pub fn add_import(&mut self, import: impl Into<Import>) {
+        self.imports.push(import.into());
+    }
+
+    pub fn with_import(mut self, import: impl Into<Import>) -> Self {
+        self.add_import(import);
+        self
+    }
+
+    pub fn with_imports(mut self, imports: impl IntoIterator<Item = impl Into<Import>>) -> Self {
+        self.imports.extend(imports.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn add_data(&mut self, data: impl Into<Dataset>) {
+        self.data.push(data.into());
     }
 
-    pub fn with_data(mut self, data: Data) -> Self {
+    pub fn with_data(mut self, data: impl Into<Dataset>) -> Self {
         self.add_data(data);
         self
     }
 
-    pub fn with_data_sets(mut self, datasets: impl IntoIterator<Item = Data>) -> Self {
-        self.data.extend(datasets);
+    pub fn with_data_sets(
+        mut self,
+        datasets: impl IntoIterator<Item = impl Into<Dataset>>,
+    ) -> Self {
+        self.data.extend(datasets.into_iter().map(Into::into));
         self
     }
 
@@ -63,6 +108,14 @@ impl Veusz {
         self
     }
 
+    /// Checks the pages for inconsistencies, like plotters bound to axes missing in their
+    /// graph. [`Veusz::open`], [`Veusz::open_session`], [`Veusz::render`] and
+    /// [`Veusz::open_saved_configuration`] fail with [`Error::Invalid`] before starting
+    /// veusz if this fails.
+    pub fn validate(&self) -> Result<()> {
+        self.pages.iter().try_for_each(Page::validate)
+    }
+
     /// Please consider [`BufWriter`] for optimal performance.
     pub fn save_configuration<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
         self.write(writer)
@@ -75,61 +128,283 @@ impl Veusz {
     }
 
     /// Warning: might not work properly or with very poor performance
-    pub fn open(self) {
-        let mut proc = std::process::Command::new("veusz")
-            .arg("--listen")
-            .stdin(std::process::Stdio::piped())
-            .stdout(std::process::Stdio::inherit())
-            .stderr(std::process::Stdio::inherit())
-            .spawn()
-            .unwrap();
+    pub fn open(self) -> Result<ExitStatus> {
+        self.validate()?;
+        let mut proc = self.launcher.spawn(self.launcher.command(&["--listen"]))?;
 
-        self.write(BufWriter::new(std::io::stdout()).borrow_mut())
-            .unwrap();
-        self.write(BufWriter::new(proc.stdin.as_mut().unwrap()).borrow_mut())
-            .unwrap();
+        self.write(BufWriter::new(std::io::stdout()).borrow_mut())?;
 
-        proc.wait().unwrap();
+        {
+            let mut writer = BufWriter::new(proc.stdin()?);
+            self.write(&mut writer)?;
+            writer.flush()?;
+        }
+
+        proc.wait()
+    }
+
+    /// Starts an interactive session which keeps veusz running and accepts further
+    /// commands and queries after this document has been sent.
+    pub fn open_session(&self) -> Result<VeuszSession> {
+        self.validate()?;
+        VeuszSession::open(self)
+    }
+
+    /// Renders all exports without showing a window and returns the produced files.
+    ///
+    /// The document is written to a temporary `.vsz` file which veusz exports once per
+    /// target with `--export` and `--quit` on the offscreen Qt platform. Existing files of
+    /// a target are renamed beforehand, so a target counts as produced if it exists
+    /// afterwards; if veusz fails, the old files are moved back. Targets with
+    /// placeholders for the page number, like `plot-%PAGE%.png`, are matched against the
+    /// files in their directory, the page number being any sequence of digits.
+    pub fn render(&self) -> Result<Vec<PathBuf>> {
+        self.validate()?;
+        let document = temporary_path("vsz");
+        let result = self.render_document(&document);
+        let _ = std::fs::remove_file(&document);
+        result
+    }
+
+    fn render_document(&self, document: &Path) -> Result<Vec<PathBuf>> {
+        {
+            let mut writer = BufWriter::new(File::create(document)?);
+            self.write_document(&mut writer)?;
+            writer.flush()?;
+        }
+
+        let mut produced = Vec::new();
+        for export in &self.exports {
+            let target = ExportTarget::new(self.launcher.working_directory(), export.filename());
+            let stale = target.set_aside()?;
+
+            let files = match self
+                .export_document(document, export)
+                .and_then(|()| target.existing())
+            {
+                Ok(files) if !files.is_empty() => files,
+                Ok(_) => {
+                    stale.restore()?;
+                    return Err(Error::MissingExport(target.path));
+                }
+                Err(e) => {
+                    stale.restore()?;
+                    return Err(e);
+                }
+            };
+            stale.discard()?;
+            produced.extend(files);
+        }
+
+        Ok(produced)
+    }
+
+    /// Runs veusz once to write the given export of the document.
+    fn export_document(&self, document: &Path, export: &Export) -> Result<()> {
+        let mut command = self.launcher.command(&[]);
+        command.arg(format!("--export={}", export.filename()));
+        for option in export.options() {
+            command.arg(format!("--export-option={option}"));
+        }
+        command.arg("--quit").arg(document);
+        if !self.launcher.has_env("QT_QPA_PLATFORM") {
+            command.env("QT_QPA_PLATFORM", "offscreen");
+        }
+
+        let mut proc = self.launcher.spawn(command)?;
+        drop(proc.take_stdin()?);
+        proc.wait()?;
+        Ok(())
+    }
+
+    /// Writes the data and pages, but not the exports.
+    fn write_document<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        for import in &self.imports {
+            import.write(writer)?;
+        }
+
+        for data in &self.data {
+            data.write(writer)?;
+        }
+
+        for page in &self.pages {
+            page.write(writer)?;
+        }
+
+        Ok(())
     }
 
     pub fn open_saved_configuration<P: AsRef<Path>>(
         self,
         path: P,
         options: &OpenOptions,
-    ) -> impl FnMut() -> std::io::Result<ExitStatus> {
-        self.write(BufWriter::new(std::io::stdout()).borrow_mut())
-            .unwrap();
-        self.write(BufWriter::new(options.open(path.as_ref()).unwrap()).borrow_mut())
-            .unwrap();
-
-        let mut proc = std::process::Command::new("veusz")
-            .arg("--listen")
-            .stdin(std::process::Stdio::piped())
-            .stdout(std::process::Stdio::inherit())
-            .stderr(std::process::Stdio::inherit())
-            .spawn()
-            .unwrap();
-
-        cmd::Load(path.as_ref().as_os_str().to_string_lossy().as_ref())
-            .write(BufWriter::new(proc.stdin.as_mut().unwrap()).borrow_mut())
-            .unwrap();
-
-        move || proc.wait()
+    ) -> Result<impl FnMut() -> Result<ExitStatus>> {
+        self.validate()?;
+        self.write(BufWriter::new(std::io::stdout()).borrow_mut())?;
+
+        {
+            let mut writer = BufWriter::new(options.open(path.as_ref())?);
+            self.write(&mut writer)?;
+            writer.flush()?;
+        }
+
+        // veusz might run in another working directory
+        let path = std::fs::canonicalize(path)?;
+        let mut proc = self.launcher.spawn(self.launcher.command(&["--listen"]))?;
+
+        {
+            let mut writer = BufWriter::new(proc.stdin()?);
+            cmd::Load(path.as_os_str().to_string_lossy().as_ref()).write(&mut writer)?;
+            writer.flush()?;
+        }
+
+        Ok(move || proc.wait())
+    }
+}
+
+/// A unique path in the temporary directory of the system, the file is not created.
+fn temporary_path(extension: &str) -> PathBuf {
+    static COUNTER: AtomicUsize = AtomicUsize::new(1);
+    std::env::temp_dir().join(format!(
+        "veusz-rs-{}-{}.{extension}",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ))
+}
+
+/// Where veusz writes an export, with placeholders like `%PAGE%` for the page number
+/// in the file name if several pages are exported to separate files.
+struct ExportTarget {
+    path: PathBuf,
+}
+
+impl ExportTarget {
+    fn new(working_directory: Option<&Path>, filename: &str) -> Self {
+        Self {
+            path: match working_directory {
+                Some(directory) => directory.join(filename),
+                None => PathBuf::from(filename),
+            },
+        }
+    }
+
+    /// The files matching this target, sorted by name.
+    fn existing(&self) -> Result<Vec<PathBuf>> {
+        let file_name = self
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        if !has_page_placeholder(&file_name) {
+            return Ok(match std::fs::metadata(&self.path) {
+                Ok(_) => vec![self.path.clone()],
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+                Err(e) => return Err(e.into()),
+            });
+        }
+
+        let directory = match self.path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        let entries = match std::fs::read_dir(directory) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut existing = Vec::new();
+        for entry in entries {
+            let entry = entry?;
+            if matches_page_pattern(&file_name, &entry.file_name().to_string_lossy()) {
+                existing.push(directory.join(entry.file_name()));
+            }
+        }
+        existing.sort();
+        Ok(existing)
+    }
+
+    /// Renames the files matching this target, so that every matching file found after
+    /// running veusz was written by it. Modification times are no help here, as they
+    /// may not change within the resolution of the file system.
+    fn set_aside(&self) -> Result<StaleExports> {
+        let mut stale = StaleExports(Vec::new());
+        for path in self.existing()? {
+            let mut backup = OsString::from(".");
+            backup.push(path.file_name().unwrap_or_default());
+            backup.push(".stale");
+            let backup = path.with_file_name(backup);
+            if let Err(e) = std::fs::rename(&path, &backup) {
+                stale.restore()?;
+                return Err(e.into());
+            }
+            stale.0.push((path, backup));
+        }
+        Ok(stale)
     }
 }
 
+/// Files of an earlier export set aside by [`ExportTarget::set_aside`], as pairs of the
+/// original and the current path.
+struct StaleExports(Vec<(PathBuf, PathBuf)>);
+
+impl StaleExports {
+    /// Moves the files back, if veusz failed to replace them.
+    fn restore(self) -> Result<()> {
+        for (path, backup) in self.0 {
+            std::fs::rename(backup, path)?;
+        }
+        Ok(())
+    }
+
+    /// Removes the files, once veusz wrote the new ones.
+    fn discard(self) -> Result<()> {
+        for (_, backup) in self.0 {
+            std::fs::remove_file(backup)?;
+        }
+        Ok(())
+    }
+}
+
+fn has_page_placeholder(file_name: &str) -> bool {
+    let percents = file_name.matches('%').count();
+    percents > 0 && percents.is_multiple_of(2)
+}
+
+/// Whether `name` is `pattern` with each `%...%` placeholder replaced by digits.
+fn matches_page_pattern(pattern: &str, name: &str) -> bool {
+    let mut parts = pattern.split('%');
+    let mut rest = match parts.next() {
+        Some(prefix) => match name.strip_prefix(prefix) {
+            Some(rest) => rest,
+            None => return false,
+        },
+        None => name,
+    };
+
+    // odd parts are the names of the placeholders, even parts literal text
+    let parts = parts.collect::<Vec<_>>();
+    for literal in parts.iter().skip(1).step_by(2) {
+        let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        if digits == 0 {
+            return false;
+        }
+        rest = &rest[digits..];
+        rest = match rest.strip_prefix(literal) {
+            Some(rest) => rest,
+            None => return false,
+        };
+    }
+    rest.is_empty()
+}
+
 impl CommandLineEmbeddingInterface for Veusz {
     fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
         // cmd::Set("colorTheme", "default-latest").write(writer)?;
         // cmd::Set("StyleSheet/axis-function/autoRange", "next-tick").write(writer)?;
 
-        for data in &self.data {
-            data.write(writer)?;
-        }
-
-        for page in &self.pages {
-            page.write(writer)?;
-        }
+        self.write_document(writer)?;
 
         for export in &self.exports {
             export.write(writer)?;
@@ -138,3 +413,158 @@ impl CommandLineEmbeddingInterface for Veusz {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::PngExport;
+    use crate::page::function::Function;
+    use crate::page::Graph;
+
+    /// Stands in for veusz, passes its arguments to `script` as `$@`.
+    fn sh(script: &str) -> Launcher {
+        Launcher::default()
+            .with_executable("sh")
+            .with_args(["-c", script, "sh"])
+    }
+
+    const TOUCH_EXPORT: &str =
+        "for arg; do case \"$arg\" in --export=*) touch \"${arg#--export=}\";; esac; done";
+
+    fn directory() -> PathBuf {
+        let directory = temporary_path("d");
+        std::fs::create_dir_all(&directory).unwrap();
+        directory
+    }
+
+    #[test]
+    fn render_creates_targets() {
+        let directory = directory();
+        let veusz = Veusz::default()
+            .with_launcher(sh(TOUCH_EXPORT).with_working_directory(&directory))
+            .with_export(PngExport::target("plot.png").with_dpi(100));
+        let produced = veusz.render().unwrap();
+        assert_eq!(vec![directory.join("plot.png")], produced);
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn render_passes_export_options() {
+        let directory = directory();
+        let veusz = Veusz::default()
+            .with_launcher(
+                sh("[ \"$2\" = --export-option=dpi=100 ] && [ \"$3\" = --quit ] && touch plot.png")
+                    .with_working_directory(&directory),
+            )
+            .with_export(PngExport::target("plot.png").with_dpi(100));
+        assert!(veusz.render().is_ok());
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn render_expands_page_placeholders() {
+        let directory = directory();
+        std::fs::write(directory.join("plot-x.png"), "").unwrap();
+        let veusz = Veusz::default()
+            .with_launcher(sh("touch plot-1.png plot-2.png").with_working_directory(&directory))
+            .with_export(PngExport::target("plot-%PAGE%.png").with_pages([0, 1]));
+        let produced = veusz.render().unwrap();
+        assert_eq!(
+            vec![directory.join("plot-1.png"), directory.join("plot-2.png")],
+            produced
+        );
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn render_keeps_but_does_not_accept_stale_targets() {
+        let directory = directory();
+        std::fs::write(directory.join("plot.png"), "old").unwrap();
+        let veusz = Veusz::default()
+            .with_launcher(sh("exit 0").with_working_directory(&directory))
+            .with_export(PngExport::target("plot.png"));
+        let result = veusz.render();
+        assert!(
+            matches!(&result, Err(Error::MissingExport(path)) if path == &directory.join("plot.png")),
+            "{result:?}"
+        );
+        assert_eq!(
+            "old",
+            std::fs::read_to_string(directory.join("plot.png")).unwrap()
+        );
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn render_accepts_targets_with_unchanged_modification_time() {
+        let directory = directory();
+        std::fs::write(directory.join("plot.png"), "old").unwrap();
+        let veusz = Veusz::default()
+            .with_launcher(
+                sh("echo new > plot.png && touch -r .plot.png.stale plot.png")
+                    .with_working_directory(&directory),
+            )
+            .with_export(PngExport::target("plot.png"));
+        assert_eq!(vec![directory.join("plot.png")], veusz.render().unwrap());
+        assert_eq!(
+            "new\n",
+            std::fs::read_to_string(directory.join("plot.png")).unwrap()
+        );
+        assert!(!directory.join(".plot.png.stale").exists());
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn render_restores_targets_on_failure() {
+        let directory = directory();
+        std::fs::write(directory.join("plot.png"), "old").unwrap();
+        let veusz = Veusz::default()
+            .with_launcher(sh("echo partial > plot.png; exit 2").with_working_directory(&directory))
+            .with_export(PngExport::target("plot.png"));
+        assert!(matches!(veusz.render(), Err(Error::NonZeroExit(..))));
+        assert_eq!(
+            "old",
+            std::fs::read_to_string(directory.join("plot.png")).unwrap()
+        );
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn render_fails_on_non_zero_exit() {
+        let directory = directory();
+        let veusz = Veusz::default()
+            .with_launcher(sh("exit 2").with_working_directory(&directory))
+            .with_export(PngExport::target("plot.png"));
+        assert!(
+            matches!(veusz.render(), Err(Error::NonZeroExit(status, _)) if status.code() == Some(2))
+        );
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn render_validates_before_starting_veusz() {
+        let directory = directory();
+        let veusz = Veusz::default()
+            .with_launcher(sh(TOUCH_EXPORT).with_working_directory(&directory))
+            .with_page(
+                Page::default().with_item(
+                    Graph::default()
+                        .with_xy_axis("x", "y")
+                        .with_item(Function::expression("x").with_y_axis("y2")),
+                ),
+            )
+            .with_export(PngExport::target("plot.png"));
+        assert!(matches!(veusz.render(), Err(Error::Invalid(_))));
+        assert!(!directory.join("plot.png").exists());
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn page_patterns() {
+        assert!(matches_page_pattern("plot-%PAGE%.png", "plot-1.png"));
+        assert!(matches_page_pattern("plot-%PAGE000%.png", "plot-012.png"));
+        assert!(!matches_page_pattern("plot-%PAGE%.png", "plot-.png"));
+        assert!(!matches_page_pattern("plot-%PAGE%.png", "plot-1.svg"));
+        assert!(!has_page_placeholder("50%.png"));
+    }
+}
@@ -0,0 +1,250 @@
+use crate::api1::{cmd, AsVueszApi1ValueStr, AutoName, PyList, PyNumber, PyStr, PyStrList};
+use crate::page::AxisBinding;
+use crate::page::AxisDirection;
+use crate::style::{ColorName, ErrorStyle};
+use crate::CommandLineEmbeddingInterface;
+use std::io::Write;
+
+/// How the bars of multiple length datasets are arranged.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum BarMode {
+    /// Side by side
+    Grouped,
+    /// On top of each other
+    Stacked,
+    /// On top of each other, as filled areas instead of bars
+    StackedArea,
+}
+
+impl AsVueszApi1ValueStr for BarMode {
+    fn as_veusz_api1_value_str(&self) -> &str {
+        match self {
+            BarMode::Grouped => "grouped",
+            BarMode::Stacked => "stacked",
+            BarMode::StackedArea => "stacked-area",
+        }
+    }
+}
+
+/// A bar chart with one bar per value of each length dataset. Errors of the length
+/// datasets are shown as error bars.
+pub struct Bar {
+    name: AutoName<Self>,
+    axes: AxisBinding,
+    lengths: Vec<String>,
+    positions: Option<String>,
+    labels: Option<String>,
+    mode: Option<BarMode>,
+    direction: Option<AxisDirection>,
+    fill_colors: Vec<String>,
+    error_style: Option<ErrorStyle>,
+    bar_fill: Option<f64>,
+    group_fill: Option<f64>,
+    key_texts: Vec<String>,
+}
+
+axis_binding!(Bar);
+
+impl Bar {
+    pub fn lengths(lengths: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            name: AutoName::default(),
+            axes: AxisBinding::default(),
+            lengths: lengths.into_iter().map(Into::into).collect(),
+            positions: None,
+            labels: None,
+            mode: None,
+            direction: None,
+            fill_colors: Vec::new(),
+            error_style: None,
+            bar_fill: None,
+            group_fill: None,
+            key_texts: Vec::new(),
+        }
+    }
+
+    /// Dataset with the positions of the bars, which are otherwise placed at 1, 2, 3...
+    pub fn set_positions(&mut self, dataset: impl Into<String>) {
+        self.positions = Some(dataset.into());
+    }
+
+    pub fn with_positions(mut self, dataset: impl Into<String>) -> Self {
+        self.set_positions(dataset);
+        self
+    }
+
+    /// Text dataset with the labels of the bars, shown on the axis.
+    pub fn set_labels(&mut self, dataset: impl Into<String>) {
+        self.labels = Some(dataset.into());
+    }
+
+    pub fn with_labels(mut self, dataset: impl Into<String>) -> Self {
+        self.set_labels(dataset);
+        self
+    }
+
+    pub fn set_mode(&mut self, mode: BarMode) {
+        self.mode = Some(mode);
+    }
+
+    pub fn with_mode(mut self, mode: BarMode) -> Self {
+        self.set_mode(mode);
+        self
+    }
+
+    /// Bars are vertical by default.
+    pub fn set_direction(&mut self, direction: AxisDirection) {
+        self.direction = Some(direction);
+    }
+
+    pub fn with_direction(mut self, direction: AxisDirection) -> Self {
+        self.set_direction(direction);
+        self
+    }
+
+    /// Fill colour of the bars of the next length dataset.
+    pub fn add_fill_color(&mut self, color: impl Into<String>) {
+        self.fill_colors.push(color.into());
+    }
+
+    pub fn with_fill_color(mut self, color: impl Into<String>) -> Self {
+        self.add_fill_color(color);
+        self
+    }
+
+    pub fn with_fill_color_by_name(mut self, color_name: ColorName) -> Self {
+        self.add_fill_color(color_name.as_veusz_api1_value_str());
+        self
+    }
+
+    pub fn with_fill_colors(mut self, colors: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.fill_colors.extend(colors.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn set_error_style(&mut self, error_style: ErrorStyle) {
+        self.error_style = Some(error_style);
+    }
+
+    pub fn with_error_style(mut self, error_style: ErrorStyle) -> Self {
+        self.set_error_style(error_style);
+        self
+    }
+
+    /// Fraction of the available space filled by a bar, from 0 to 1.
+    pub fn set_bar_fill(&mut self, fraction: f64) {
+        self.bar_fill = Some(fraction);
+    }
+
+    pub fn with_bar_fill(mut self, fraction: f64) -> Self {
+        self.set_bar_fill(fraction);
+        self
+    }
+
+    /// Fraction of the available space filled by a group of bars, from 0 to 1.
+    pub fn set_group_fill(&mut self, fraction: f64) {
+        self.group_fill = Some(fraction);
+    }
+
+    pub fn with_group_fill(mut self, fraction: f64) -> Self {
+        self.set_group_fill(fraction);
+        self
+    }
+
+    /// Text shown in the key of the graph for the next length dataset.
+    pub fn add_key_text(&mut self, text: impl Into<String>) {
+        self.key_texts.push(text.into());
+    }
+
+    pub fn with_key_text(mut self, text: impl Into<String>) -> Self {
+        self.add_key_text(text);
+        self
+    }
+
+    pub fn with_key_texts(mut self, texts: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.key_texts.extend(texts.into_iter().map(Into::into));
+        self
+    }
+}
+
+impl CommandLineEmbeddingInterface for Bar {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        cmd::Add("bar", &self.name).write(writer)?;
+        cmd::ToUnique(&self.name).for_call(writer, |writer| {
+            cmd::SetRaw("lengths", PyStrList(&self.lengths)).write(writer)?;
+
+            if let Some(positions) = &self.positions {
+                cmd::Set("posn", positions).write(writer)?;
+            }
+
+            if let Some(labels) = &self.labels {
+                cmd::Set("labels", labels).write(writer)?;
+            }
+
+            if let Some(mode) = &self.mode {
+                cmd::Set("mode", mode.as_veusz_api1_value_str()).write(writer)?;
+            }
+
+            if let Some(direction) = &self.direction {
+                cmd::Set("direction", direction.as_str()).write(writer)?;
+            }
+
+            if !self.fill_colors.is_empty() {
+                let fills = self
+                    .fill_colors
+                    .iter()
+                    .map(|color| format!("('solid', {}, False)", PyStr(color)));
+                cmd::SetRaw("BarFill/fills", PyList(fills)).write(writer)?;
+            }
+
+            if let Some(error_style) = &self.error_style {
+                cmd::Set("errorstyle", error_style.as_veusz_api1_value_str()).write(writer)?;
+            }
+
+            if let Some(bar_fill) = self.bar_fill {
+                cmd::SetRaw("barfill", PyNumber(bar_fill)).write(writer)?;
+            }
+
+            if let Some(group_fill) = self.group_fill {
+                cmd::SetRaw("groupfill", PyNumber(group_fill)).write(writer)?;
+            }
+
+            if !self.key_texts.is_empty() {
+                cmd::SetRaw("keys", PyStrList(&self.key_texts)).write(writer)?;
+            }
+
+            self.axes.write(writer)?;
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api1::written;
+
+    #[test]
+    fn stacked_bars() {
+        let bar = Bar::lengths(["rust", "c"])
+            .with_labels("benchmarks")
+            .with_mode(BarMode::Stacked)
+            .with_direction(AxisDirection::Horizontal)
+            .with_fill_color("#ff8000")
+            .with_fill_color_by_name(ColorName::Blue)
+            .with_bar_fill(0.5);
+        assert_eq!(
+            "Add(u'bar', name=NAME, autoadd=False)\n\
+             To(NAME)\n\
+             Set(u'lengths', [u'rust', u'c'])\n\
+             Set(u'labels', u'benchmarks')\n\
+             Set(u'mode', u'stacked')\n\
+             Set(u'direction', u'horizontal')\n\
+             Set(u'BarFill/fills', [('solid', u'#ff8000', False), ('solid', u'blue', False)])\n\
+             Set(u'barfill', 0.5)\n\
+             To('..')\n",
+            written(&bar, &bar.name)
+        );
+    }
+}
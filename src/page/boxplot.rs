@@ -0,0 +1,327 @@
+use crate::api1::{cmd, AsVueszApi1ValueStr, AutoName, PyNumber, PyStrList};
+use crate::page::AxisBinding;
+use crate::page::AxisDirection;
+use crate::style::fill::Fill;
+use crate::style::line::Line;
+use crate::style::marker::Marker;
+use crate::CommandLineEmbeddingInterface;
+use std::io::Write;
+
+/// How far the whiskers of a [`BoxPlot`] calculated from raw values reach.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum WhiskerMode {
+    MinMax,
+    /// 1.5 times the inter-quartile range
+    InterQuartileRange,
+    StandardDeviation,
+    Percentile9To91,
+    Percentile2To98,
+}
+
+impl AsVueszApi1ValueStr for WhiskerMode {
+    fn as_veusz_api1_value_str(&self) -> &str {
+        match self {
+            WhiskerMode::MinMax => "min/max",
+            WhiskerMode::InterQuartileRange => "1.5IQR",
+            WhiskerMode::StandardDeviation => "1 stddev",
+            WhiskerMode::Percentile9To91 => "9/91 percentile",
+            WhiskerMode::Percentile2To98 => "2/98 percentile",
+        }
+    }
+}
+
+/// Datasets with one precomputed statistic per box.
+pub struct BoxStatistics {
+    pub median: String,
+    /// Lower quartile
+    pub box_min: String,
+    /// Upper quartile
+    pub box_max: String,
+    pub whisker_min: String,
+    pub whisker_max: String,
+    pub mean: Option<String>,
+}
+
+enum Source {
+    Values(Vec<String>),
+    Statistics(BoxStatistics),
+}
+
+/// Box and whisker plot, either calculated by veusz with one box per dataset of raw
+/// values or drawn from precomputed statistics.
+pub struct BoxPlot {
+    name: AutoName<Self>,
+    axes: AxisBinding,
+    source: Source,
+    whisker_mode: Option<WhiskerMode>,
+    positions: Option<String>,
+    labels: Option<String>,
+    direction: Option<AxisDirection>,
+    fill_fraction: Option<f64>,
+    outliers_marker: Option<Marker>,
+    mean_marker: Option<Marker>,
+    marker_size: Option<f32>,
+    fill: Option<Fill>,
+    border: Option<Line>,
+    whisker: Option<Line>,
+}
+
+axis_binding!(BoxPlot);
+
+impl BoxPlot {
+    /// One box for each dataset of raw values.
+    pub fn values(datasets: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self::new(Source::Values(
+            datasets.into_iter().map(Into::into).collect(),
+        ))
+    }
+
+    /// One box for each value of the statistics datasets.
+    pub fn statistics(statistics: BoxStatistics) -> Self {
+        Self::new(Source::Statistics(statistics))
+    }
+
+    fn new(source: Source) -> Self {
+        Self {
+            name: AutoName::default(),
+            axes: AxisBinding::default(),
+            source,
+            whisker_mode: None,
+            positions: None,
+            labels: None,
+            direction: None,
+            fill_fraction: None,
+            outliers_marker: None,
+            mean_marker: None,
+            marker_size: None,
+            fill: None,
+            border: None,
+            whisker: None,
+        }
+    }
+
+    /// Only used for boxes calculated from raw values.
+    pub fn set_whisker_mode(&mut self, mode: WhiskerMode) {
+        self.whisker_mode = Some(mode);
+    }
+
+    pub fn with_whisker_mode(mut self, mode: WhiskerMode) -> Self {
+        self.set_whisker_mode(mode);
+        self
+    }
+
+    /// Dataset with the positions of the boxes, which are otherwise placed at 1, 2, 3...
+    pub fn set_positions(&mut self, dataset: impl Into<String>) {
+        self.positions = Some(dataset.into());
+    }
+
+    pub fn with_positions(mut self, dataset: impl Into<String>) -> Self {
+        self.set_positions(dataset);
+        self
+    }
+
+    /// Text dataset with the labels of the boxes, shown on the axis.
+    pub fn set_labels(&mut self, dataset: impl Into<String>) {
+        self.labels = Some(dataset.into());
+    }
+
+    pub fn with_labels(mut self, dataset: impl Into<String>) -> Self {
+        self.set_labels(dataset);
+        self
+    }
+
+    /// Boxes are vertical by default.
+    pub fn set_direction(&mut self, direction: AxisDirection) {
+        self.direction = Some(direction);
+    }
+
+    pub fn with_direction(mut self, direction: AxisDirection) -> Self {
+        self.set_direction(direction);
+        self
+    }
+
+    /// Fraction of the available space filled by a box, from 0 to 1.
+    pub fn set_fill_fraction(&mut self, fraction: f64) {
+        self.fill_fraction = Some(fraction);
+    }
+
+    pub fn with_fill_fraction(mut self, fraction: f64) -> Self {
+        self.set_fill_fraction(fraction);
+        self
+    }
+
+    pub fn set_outliers_marker(&mut self, marker: Marker) {
+        self.outliers_marker = Some(marker);
+    }
+
+    pub fn with_outliers_marker(mut self, marker: Marker) -> Self {
+        self.set_outliers_marker(marker);
+        self
+    }
+
+    pub fn set_mean_marker(&mut self, marker: Marker) {
+        self.mean_marker = Some(marker);
+    }
+
+    pub fn with_mean_marker(mut self, marker: Marker) -> Self {
+        self.set_mean_marker(marker);
+        self
+    }
+
+    pub fn set_marker_size(&mut self, pt: f32) {
+        self.marker_size = Some(pt);
+    }
+
+    pub fn with_marker_size(mut self, pt: f32) -> Self {
+        self.set_marker_size(pt);
+        self
+    }
+
+    pub fn set_fill(&mut self, fill: Fill) {
+        self.fill = Some(fill);
+    }
+
+    pub fn with_fill(mut self, fill: Fill) -> Self {
+        self.set_fill(fill);
+        self
+    }
+
+    pub fn set_border(&mut self, border: Line) {
+        self.border = Some(border);
+    }
+
+    pub fn with_border(mut self, border: Line) -> Self {
+        self.set_border(border);
+        self
+    }
+
+    pub fn set_whisker(&mut self, whisker: Line) {
+        self.whisker = Some(whisker);
+    }
+
+    pub fn with_whisker(mut self, whisker: Line) -> Self {
+        self.set_whisker(whisker);
+        self
+    }
+}
+
+impl CommandLineEmbeddingInterface for BoxPlot {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        cmd::Add("boxplot", &self.name).write(writer)?;
+        cmd::ToUnique(&self.name).for_call(writer, |writer| {
+            match &self.source {
+                Source::Values(datasets) => {
+                    cmd::SetRaw("values", PyStrList(datasets)).write(writer)?;
+                    if let Some(mode) = &self.whisker_mode {
+                        cmd::Set("whiskermode", mode.as_veusz_api1_value_str()).write(writer)?;
+                    }
+                }
+                Source::Statistics(statistics) => {
+                    cmd::SetRaw("calculate", false.as_veusz_api1_value_str()).write(writer)?;
+                    cmd::Set("median", &statistics.median).write(writer)?;
+                    cmd::Set("boxmin", &statistics.box_min).write(writer)?;
+                    cmd::Set("boxmax", &statistics.box_max).write(writer)?;
+                    cmd::Set("whiskermin", &statistics.whisker_min).write(writer)?;
+                    cmd::Set("whiskermax", &statistics.whisker_max).write(writer)?;
+                    if let Some(mean) = &statistics.mean {
+                        cmd::Set("mean", mean).write(writer)?;
+                    }
+                }
+            }
+
+            if let Some(positions) = &self.positions {
+                cmd::Set("posn", positions).write(writer)?;
+            }
+
+            if let Some(labels) = &self.labels {
+                cmd::Set("labels", labels).write(writer)?;
+            }
+
+            if let Some(direction) = &self.direction {
+                cmd::Set("direction", direction.as_str()).write(writer)?;
+            }
+
+            if let Some(fraction) = self.fill_fraction {
+                cmd::SetRaw("fillfraction", PyNumber(fraction)).write(writer)?;
+            }
+
+            if let Some(marker) = &self.outliers_marker {
+                cmd::Set("outliersmarker", marker.as_veusz_api1_value_str()).write(writer)?;
+            }
+
+            if let Some(marker) = &self.mean_marker {
+                cmd::Set("meanmarker", marker.as_veusz_api1_value_str()).write(writer)?;
+            }
+
+            if let Some(size) = self.marker_size {
+                cmd::Set("markerSize", &format!("{size}pt")).write(writer)?;
+            }
+
+            if let Some(fill) = &self.fill {
+                fill.write_as("Fill", writer)?;
+            }
+
+            if let Some(border) = &self.border {
+                border.write_as("Border", writer)?;
+            }
+
+            if let Some(whisker) = &self.whisker {
+                whisker.write_as("Whisker", writer)?;
+            }
+
+            self.axes.write(writer)?;
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api1::written;
+
+    #[test]
+    fn calculated_from_values() {
+        let boxplot = BoxPlot::values(["latency_a", "latency_b"])
+            .with_whisker_mode(WhiskerMode::Percentile2To98)
+            .with_outliers_marker(Marker::Cross)
+            .with_border(Line::default().with_width(0.5));
+        assert_eq!(
+            "Add(u'boxplot', name=NAME, autoadd=False)\n\
+             To(NAME)\n\
+             Set(u'values', [u'latency_a', u'latency_b'])\n\
+             Set(u'whiskermode', u'2/98 percentile')\n\
+             Set(u'outliersmarker', u'cross')\n\
+             Set(u'Border/width', u'0.5pt')\n\
+             To('..')\n",
+            written(&boxplot, &boxplot.name)
+        );
+    }
+
+    #[test]
+    fn precomputed_statistics() {
+        let boxplot = BoxPlot::statistics(BoxStatistics {
+            median: "p50".into(),
+            box_min: "p25".into(),
+            box_max: "p75".into(),
+            whisker_min: "p1".into(),
+            whisker_max: "p99".into(),
+            mean: None,
+        })
+        .with_labels("names");
+        assert_eq!(
+            "Add(u'boxplot', name=NAME, autoadd=False)\n\
+             To(NAME)\n\
+             Set(u'calculate', False)\n\
+             Set(u'median', u'p50')\n\
+             Set(u'boxmin', u'p25')\n\
+             Set(u'boxmax', u'p75')\n\
+             Set(u'whiskermin', u'p1')\n\
+             Set(u'whiskermax', u'p99')\n\
+             Set(u'labels', u'names')\n\
+             To('..')\n",
+            written(&boxplot, &boxplot.name)
+        );
+    }
+}
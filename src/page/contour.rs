@@ -0,0 +1,302 @@
+use crate::api1::{cmd, AsVueszApi1ValueStr, AutoName, PyList, PyNumber};
+use crate::page::image::ColorScaling;
+use crate::page::AxisBinding;
+use crate::style::fill::Fill;
+use crate::style::line::Line;
+use crate::style::ColorMap;
+use crate::CommandLineEmbeddingInterface;
+use std::io::Write;
+
+/// Where the contours of a [`Contour`] are drawn.
+#[derive(Clone, PartialEq)]
+pub enum Levels {
+    /// Number of levels spread between the minimum and maximum by the scaling
+    Count(u32),
+    /// Explicit values of the levels, ignoring the scaling
+    Manual(Vec<f64>),
+}
+
+/// Contour lines of a two dimensional dataset, optionally with the areas between the
+/// levels filled.
+pub struct Contour {
+    name: AutoName<Self>,
+    axes: AxisBinding,
+    data: String,
+    levels: Option<Levels>,
+    scaling: Option<ColorScaling>,
+    min: Option<f64>,
+    max: Option<f64>,
+    lines: Vec<Line>,
+    fills: Vec<Fill>,
+    fill_color_map: Option<ColorMap>,
+    sub_contours: Option<u32>,
+    sub_lines: Vec<Line>,
+    label_format: Option<String>,
+    label_scale: Option<f64>,
+}
+
+axis_binding!(Contour);
+
+impl Contour {
+    pub fn data(data: impl Into<String>) -> Self {
+        Self {
+            name: AutoName::default(),
+            axes: AxisBinding::default(),
+            data: data.into(),
+            levels: None,
+            scaling: None,
+            min: None,
+            max: None,
+            lines: Vec::new(),
+            fills: Vec::new(),
+            fill_color_map: None,
+            sub_contours: None,
+            sub_lines: Vec::new(),
+            label_format: None,
+            label_scale: None,
+        }
+    }
+
+    pub fn set_levels(&mut self, levels: Levels) {
+        self.levels = Some(levels);
+    }
+
+    pub fn with_levels(mut self, levels: Levels) -> Self {
+        self.set_levels(levels);
+        self
+    }
+
+    /// Spacing of counted levels, linear by default.
+    pub fn set_scaling(&mut self, scaling: ColorScaling) {
+        self.scaling = Some(scaling);
+    }
+
+    pub fn with_scaling(mut self, scaling: ColorScaling) -> Self {
+        self.set_scaling(scaling);
+        self
+    }
+
+    /// Lowest counted level, defaults to the minimum of the data.
+    pub fn set_min(&mut self, min: f64) {
+        self.min = Some(min);
+    }
+
+    pub fn with_min(mut self, min: f64) -> Self {
+        self.set_min(min);
+        self
+    }
+
+    /// Highest counted level, defaults to the maximum of the data.
+    pub fn set_max(&mut self, max: f64) {
+        self.max = Some(max);
+    }
+
+    pub fn with_max(mut self, max: f64) -> Self {
+        self.set_max(max);
+        self
+    }
+
+    /// Line of the next level. veusz repeats the lines if there are more levels.
+    pub fn add_line(&mut self, line: Line) {
+        self.lines.push(line);
+    }
+
+    pub fn with_line(mut self, line: Line) -> Self {
+        self.add_line(line);
+        self
+    }
+
+    pub fn with_lines(mut self, lines: impl IntoIterator<Item = Line>) -> Self {
+        self.lines.extend(lines);
+        self
+    }
+
+    /// Fill of the area between the next two levels.
+    pub fn add_fill(&mut self, fill: Fill) {
+        self.fills.push(fill);
+    }
+
+    pub fn with_fill(mut self, fill: Fill) -> Self {
+        self.add_fill(fill);
+        self
+    }
+
+    pub fn with_fills(mut self, fills: impl IntoIterator<Item = Fill>) -> Self {
+        self.fills.extend(fills);
+        self
+    }
+
+    /// Fills the areas between the levels with the colours of the given map instead of
+    /// explicit fills.
+    pub fn set_fill_color_map(&mut self, color_map: ColorMap) {
+        self.fill_color_map = Some(color_map);
+    }
+
+    pub fn with_fill_color_map(mut self, color_map: ColorMap) -> Self {
+        self.set_fill_color_map(color_map);
+        self
+    }
+
+    /// Number of sub-contours drawn between each two levels.
+    pub fn set_sub_contours(&mut self, subdivisions: u32) {
+        self.sub_contours = Some(subdivisions);
+    }
+
+    pub fn with_sub_contours(mut self, subdivisions: u32) -> Self {
+        self.set_sub_contours(subdivisions);
+        self
+    }
+
+    pub fn add_sub_line(&mut self, line: Line) {
+        self.sub_lines.push(line);
+    }
+
+    pub fn with_sub_line(mut self, line: Line) -> Self {
+        self.add_sub_line(line);
+        self
+    }
+
+    /// Labels the contours with their level, formatted like `%.3Vg` or `%.2f`.
+    pub fn set_labels(&mut self, format: impl Into<String>) {
+        self.label_format = Some(format.into());
+    }
+
+    pub fn with_labels(mut self, format: impl Into<String>) -> Self {
+        self.set_labels(format);
+        self
+    }
+
+    /// Factor applied to the levels before they are formatted as label.
+    pub fn set_label_scale(&mut self, scale: f64) {
+        self.label_scale = Some(scale);
+    }
+
+    pub fn with_label_scale(mut self, scale: f64) -> Self {
+        self.set_label_scale(scale);
+        self
+    }
+}
+
+impl CommandLineEmbeddingInterface for Contour {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        cmd::Add("contour", &self.name).write(writer)?;
+        cmd::ToUnique(&self.name).for_call(writer, |writer| {
+            cmd::Set("data", &self.data).write(writer)?;
+
+            if let Some(scaling) = &self.scaling {
+                cmd::Set("scaling", scaling.as_veusz_api1_value_str()).write(writer)?;
+            }
+
+            match &self.levels {
+                None => {}
+                Some(Levels::Count(count)) => cmd::SetRaw("numLevels", count).write(writer)?,
+                Some(Levels::Manual(levels)) => {
+                    cmd::Set("scaling", "manual").write(writer)?;
+                    cmd::SetRaw("manualLevels", PyList(levels.iter().map(PyNumber)))
+                        .write(writer)?;
+                }
+            }
+
+            if let Some(min) = self.min {
+                cmd::SetRaw("min", PyNumber(min)).write(writer)?;
+            }
+
+            if let Some(max) = self.max {
+                cmd::SetRaw("max", PyNumber(max)).write(writer)?;
+            }
+
+            if !self.lines.is_empty() {
+                write_set(
+                    "Lines/lines",
+                    self.lines.iter().map(Line::line_set_item),
+                    writer,
+                )?;
+            }
+
+            if !self.fills.is_empty() {
+                write_set(
+                    "Fills/fills",
+                    self.fills.iter().map(Fill::fill_set_item),
+                    writer,
+                )?;
+            }
+
+            if let Some(color_map) = &self.fill_color_map {
+                cmd::Set("Fills/colorMap", color_map.as_veusz_api1_value_str()).write(writer)?;
+            }
+
+            if !self.fills.is_empty() || self.fill_color_map.is_some() {
+                cmd::SetRaw("Fills/hide", false.as_veusz_api1_value_str()).write(writer)?;
+            }
+
+            if let Some(subdivisions) = self.sub_contours {
+                cmd::SetRaw("subdivisions", subdivisions).write(writer)?;
+                cmd::SetRaw("SubLines/hide", false.as_veusz_api1_value_str()).write(writer)?;
+            }
+
+            if !self.sub_lines.is_empty() {
+                write_set(
+                    "SubLines/lines",
+                    self.sub_lines.iter().map(Line::line_set_item),
+                    writer,
+                )?;
+            }
+
+            if let Some(format) = &self.label_format {
+                cmd::Set("ContourLabels/format", format).write(writer)?;
+                cmd::SetRaw("ContourLabels/hide", false.as_veusz_api1_value_str()).write(writer)?;
+            }
+
+            if let Some(scale) = self.label_scale {
+                cmd::SetRaw("ContourLabels/scale", PyNumber(scale)).write(writer)?;
+            }
+
+            self.axes.write(writer)?;
+
+            Ok(())
+        })
+    }
+}
+
+fn write_set<W: Write>(
+    path: &str,
+    items: impl Iterator<Item = String>,
+    writer: &mut W,
+) -> std::io::Result<()> {
+    cmd::SetRaw(path, PyList(&items.collect::<Vec<_>>())).write(writer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api1::written;
+    use crate::style::line::LineStyle;
+
+    #[test]
+    fn manual_levels_with_labels() {
+        let contour = Contour::data("field")
+            .with_levels(Levels::Manual(vec![-1.0, 0.0, 0.5]))
+            .with_line(Line::default().with_style(LineStyle::Dashed))
+            .with_line(Line::default().with_color("red").with_width(2.0))
+            .with_fill_color_map(ColorMap::CoolWarm)
+            .with_sub_contours(2)
+            .with_labels("%.1f");
+        assert_eq!(
+            "Add(u'contour', name=NAME, autoadd=False)\n\
+             To(NAME)\n\
+             Set(u'data', u'field')\n\
+             Set(u'scaling', u'manual')\n\
+             Set(u'manualLevels', [-1, 0, 0.5])\n\
+             Set(u'Lines/lines', [(u'dashed', u'1pt', u'black', False), \
+             (u'solid', u'2pt', u'red', False)])\n\
+             Set(u'Fills/colorMap', u'cool-warm')\n\
+             Set(u'Fills/hide', False)\n\
+             Set(u'subdivisions', 2)\n\
+             Set(u'SubLines/hide', False)\n\
+             Set(u'ContourLabels/format', u'%.1f')\n\
+             Set(u'ContourLabels/hide', False)\n\
+             To('..')\n",
+            written(&contour, &contour.name)
+        );
+    }
+}
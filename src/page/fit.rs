@@ -0,0 +1,237 @@
+use crate::api1::{cmd, AsVueszApi1ValueStr, AutoName, PyNumber, PyStr};
+use crate::page::function::Variable;
+use crate::page::AxisBinding;
+use crate::style::plot::PlotLine;
+use crate::CommandLineEmbeddingInterface;
+use std::io::Write;
+
+/// How the errors of data points without error bars are estimated when fitting.
+#[derive(Copy, Clone, PartialEq)]
+pub enum DefaultError {
+    /// The given fraction of the absolute value of each point
+    Absolute(f64),
+    /// The given value for each point
+    Constant(f64),
+    /// The given fraction of each value
+    Relative(f64),
+}
+
+impl AsVueszApi1ValueStr for DefaultError {
+    fn as_veusz_api1_value_str(&self) -> &str {
+        match self {
+            DefaultError::Absolute(_) => "absolute",
+            DefaultError::Constant(_) => "constant",
+            DefaultError::Relative(_) => "relative",
+        }
+    }
+}
+
+/// Fits an expression like `a + b * x` to a dataset and plots it with the fitted
+/// parameters. The fit itself is run with
+/// [`VeuszSession::fit`](crate::session::VeuszSession::fit) or from the user interface.
+pub struct Fit {
+    name: AutoName<Self>,
+    axes: AxisBinding,
+    expression: String,
+    parameters: Vec<(String, f64)>,
+    x_data: String,
+    y_data: String,
+    variable: Option<Variable>,
+    min: Option<f64>,
+    max: Option<f64>,
+    fit_range: Option<bool>,
+    default_error: Option<DefaultError>,
+    plot_line: Option<PlotLine>,
+    key_text: Option<String>,
+}
+
+axis_binding!(Fit);
+
+impl Fit {
+    pub fn expression(
+        expression: impl Into<String>,
+        x_data: impl Into<String>,
+        y_data: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: AutoName::default(),
+            axes: AxisBinding::default(),
+            expression: expression.into(),
+            parameters: Vec::new(),
+            x_data: x_data.into(),
+            y_data: y_data.into(),
+            variable: None,
+            min: None,
+            max: None,
+            fit_range: None,
+            default_error: None,
+            plot_line: None,
+            key_text: None,
+        }
+    }
+
+    /// The name of the widget, to build its path for [`VeuszSession::fit`](crate::session::VeuszSession::fit).
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// A parameter of the expression with its initial value.
+    pub fn add_parameter(&mut self, name: impl Into<String>, initial: f64) {
+        self.parameters.push((name.into(), initial));
+    }
+
+    pub fn with_parameter(mut self, name: impl Into<String>, initial: f64) -> Self {
+        self.add_parameter(name, initial);
+        self
+    }
+
+    pub fn set_variable(&mut self, variable: Variable) {
+        self.variable = Some(variable);
+    }
+
+    pub fn with_variable(mut self, variable: Variable) -> Self {
+        self.set_variable(variable);
+        self
+    }
+
+    /// Lower end of the plotted and, with [`Fit::with_fit_range`], fitted range.
+    pub fn set_min(&mut self, min: f64) {
+        self.min = Some(min);
+    }
+
+    pub fn with_min(mut self, min: f64) -> Self {
+        self.set_min(min);
+        self
+    }
+
+    /// Upper end of the plotted and, with [`Fit::with_fit_range`], fitted range.
+    pub fn set_max(&mut self, max: f64) {
+        self.max = Some(max);
+    }
+
+    pub fn with_max(mut self, max: f64) -> Self {
+        self.set_max(max);
+        self
+    }
+
+    /// Only fit the data between the minimum and maximum.
+    pub fn set_fit_range(&mut self, fit_range: bool) {
+        self.fit_range = Some(fit_range);
+    }
+
+    pub fn with_fit_range(mut self, fit_range: bool) -> Self {
+        self.set_fit_range(fit_range);
+        self
+    }
+
+    pub fn set_default_error(&mut self, error: DefaultError) {
+        self.default_error = Some(error);
+    }
+
+    pub fn with_default_error(mut self, error: DefaultError) -> Self {
+        self.set_default_error(error);
+        self
+    }
+
+    pub fn set_plot_line(&mut self, plot_line: PlotLine) {
+        self.plot_line = Some(plot_line);
+    }
+
+    pub fn with_plot_line(mut self, plot_line: PlotLine) -> Self {
+        self.set_plot_line(plot_line);
+        self
+    }
+
+    /// Text shown for this fit in the key of the graph.
+    pub fn set_key_text(&mut self, text: impl Into<String>) {
+        self.key_text = Some(text.into());
+    }
+
+    pub fn with_key_text(mut self, text: impl Into<String>) -> Self {
+        self.set_key_text(text);
+        self
+    }
+}
+
+impl CommandLineEmbeddingInterface for Fit {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        cmd::Add("fit", &self.name).write(writer)?;
+        cmd::ToUnique(&self.name).for_call(writer, |writer| {
+            cmd::Set("function", &self.expression).write(writer)?;
+
+            if !self.parameters.is_empty() {
+                let values = self
+                    .parameters
+                    .iter()
+                    .map(|(name, value)| format!("{}: {}", PyStr(name), PyNumber(value)))
+                    .collect::<Vec<_>>();
+                cmd::SetRaw("values", format!("{{{}}}", values.join(", "))).write(writer)?;
+            }
+
+            cmd::Set("xData", &self.x_data).write(writer)?;
+            cmd::Set("yData", &self.y_data).write(writer)?;
+
+            if let Some(variable) = &self.variable {
+                cmd::Set("variable", variable.as_str()).write(writer)?;
+            }
+
+            if let Some(min) = self.min {
+                cmd::SetRaw("min", PyNumber(min)).write(writer)?;
+            }
+
+            if let Some(max) = self.max {
+                cmd::SetRaw("max", PyNumber(max)).write(writer)?;
+            }
+
+            if let Some(fit_range) = &self.fit_range {
+                cmd::SetRaw("fitRange", fit_range.as_veusz_api1_value_str()).write(writer)?;
+            }
+
+            if let Some(error) = &self.default_error {
+                let (DefaultError::Absolute(value)
+                | DefaultError::Constant(value)
+                | DefaultError::Relative(value)) = error;
+                cmd::Set("defErrType", error.as_veusz_api1_value_str()).write(writer)?;
+                cmd::SetRaw("defErr", PyNumber(value)).write(writer)?;
+            }
+
+            if let Some(plot_line) = &self.plot_line {
+                plot_line.write(writer)?;
+            }
+
+            if let Some(text) = &self.key_text {
+                cmd::Set("key", text).write(writer)?;
+            }
+
+            self.axes.write(writer)?;
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api1::written;
+
+    #[test]
+    fn fit_with_parameters() {
+        let fit = Fit::expression("a + b * x", "x", "y")
+            .with_parameter("a", 0.0)
+            .with_parameter("b", 1.5)
+            .with_default_error(DefaultError::Relative(0.1));
+        assert_eq!(
+            "Add(u'fit', name=NAME, autoadd=False)\n\
+             To(NAME)\n\
+             Set(u'function', u'a + b * x')\n\
+             Set(u'values', {u'a': 0, u'b': 1.5})\n\
+             Set(u'xData', u'x')\n\
+             Set(u'yData', u'y')\n\
+             Set(u'defErrType', u'relative')\n\
+             Set(u'defErr', 0.1)\n\
+             To('..')\n",
+            written(&fit, fit.name())
+        );
+    }
+}
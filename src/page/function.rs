@@ -0,0 +1,208 @@
+use crate::api1::{cmd, AutoName, PyNumber};
+use crate::page::AxisBinding;
+use crate::style::fill::Fill;
+use crate::style::plot::PlotLine;
+use crate::CommandLineEmbeddingInterface;
+use std::io::Write;
+
+/// The variable of the expression of a [`Function`].
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum Variable {
+    /// `y = f(x)`
+    X,
+    /// `x = f(y)`
+    Y,
+}
+
+impl Variable {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Variable::X => "x",
+            Variable::Y => "y",
+        }
+    }
+}
+
+/// Plots an analytic expression like `a * exp(-x / tau)`, evaluated by veusz.
+pub struct Function {
+    name: AutoName<Self>,
+    axes: AxisBinding,
+    expression: String,
+    variable: Option<Variable>,
+    min: Option<f64>,
+    max: Option<f64>,
+    steps: Option<u32>,
+    plot_line: Option<PlotLine>,
+    fill_below: Option<Fill>,
+    fill_above: Option<Fill>,
+    key_text: Option<String>,
+}
+
+axis_binding!(Function);
+
+impl Function {
+    pub fn expression(expression: impl Into<String>) -> Self {
+        Self {
+            name: AutoName::default(),
+            axes: AxisBinding::default(),
+            expression: expression.into(),
+            variable: None,
+            min: None,
+            max: None,
+            steps: None,
+            plot_line: None,
+            fill_below: None,
+            fill_above: None,
+            key_text: None,
+        }
+    }
+
+    pub fn set_variable(&mut self, variable: Variable) {
+        self.variable = Some(variable);
+    }
+
+    pub fn with_variable(mut self, variable: Variable) -> Self {
+        self.set_variable(variable);
+        self
+    }
+
+    /// Lower end of the variable, defaults to the minimum of its axis.
+    pub fn set_min(&mut self, min: f64) {
+        self.min = Some(min);
+    }
+
+    pub fn with_min(mut self, min: f64) -> Self {
+        self.set_min(min);
+        self
+    }
+
+    /// Upper end of the variable, defaults to the maximum of its axis.
+    pub fn set_max(&mut self, max: f64) {
+        self.max = Some(max);
+    }
+
+    pub fn with_max(mut self, max: f64) -> Self {
+        self.set_max(max);
+        self
+    }
+
+    /// Number of points the expression is evaluated at.
+    pub fn set_steps(&mut self, steps: u32) {
+        self.steps = Some(steps);
+    }
+
+    pub fn with_steps(mut self, steps: u32) -> Self {
+        self.set_steps(steps);
+        self
+    }
+
+    pub fn set_plot_line(&mut self, plot_line: PlotLine) {
+        self.plot_line = Some(plot_line);
+    }
+
+    pub fn with_plot_line(mut self, plot_line: PlotLine) -> Self {
+        self.set_plot_line(plot_line);
+        self
+    }
+
+    pub fn set_fill_below(&mut self, fill: Fill) {
+        self.fill_below = Some(fill);
+    }
+
+    pub fn with_fill_below(mut self, fill: Fill) -> Self {
+        self.set_fill_below(fill);
+        self
+    }
+
+    pub fn set_fill_above(&mut self, fill: Fill) {
+        self.fill_above = Some(fill);
+    }
+
+    pub fn with_fill_above(mut self, fill: Fill) -> Self {
+        self.set_fill_above(fill);
+        self
+    }
+
+    /// Text shown for this function in the key of the graph.
+    pub fn set_key_text(&mut self, text: impl Into<String>) {
+        self.key_text = Some(text.into());
+    }
+
+    pub fn with_key_text(mut self, text: impl Into<String>) -> Self {
+        self.set_key_text(text);
+        self
+    }
+}
+
+impl CommandLineEmbeddingInterface for Function {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        cmd::Add("function", &self.name).write(writer)?;
+        cmd::ToUnique(&self.name).for_call(writer, |writer| {
+            cmd::Set("function", &self.expression).write(writer)?;
+
+            if let Some(variable) = &self.variable {
+                cmd::Set("variable", variable.as_str()).write(writer)?;
+            }
+
+            if let Some(min) = self.min {
+                cmd::SetRaw("min", PyNumber(min)).write(writer)?;
+            }
+
+            if let Some(max) = self.max {
+                cmd::SetRaw("max", PyNumber(max)).write(writer)?;
+            }
+
+            if let Some(steps) = self.steps {
+                cmd::SetRaw("steps", steps).write(writer)?;
+            }
+
+            if let Some(plot_line) = &self.plot_line {
+                plot_line.write(writer)?;
+            }
+
+            if let Some(fill) = &self.fill_below {
+                fill.write_as("FillBelow", writer)?;
+            }
+
+            if let Some(fill) = &self.fill_above {
+                fill.write_as("FillAbove", writer)?;
+            }
+
+            if let Some(text) = &self.key_text {
+                cmd::Set("key", text).write(writer)?;
+            }
+
+            self.axes.write(writer)?;
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api1::written;
+    use crate::style::fill::FillStyle;
+
+    #[test]
+    fn function_with_fill() {
+        let function = Function::expression("exp(-x)")
+            .with_max(2.0)
+            .with_steps(100)
+            .with_fill_below(Fill::default().with_style(FillStyle::Cross))
+            .with_key_text("model");
+        assert_eq!(
+            "Add(u'function', name=NAME, autoadd=False)\n\
+             To(NAME)\n\
+             Set(u'function', u'exp(-x)')\n\
+             Set(u'max', 2)\n\
+             Set(u'steps', 100)\n\
+             Set(u'FillBelow/style', u'cross')\n\
+             Set(u'FillBelow/hide', False)\n\
+             Set(u'key', u'model')\n\
+             To('..')\n",
+            written(&function, &function.name)
+        );
+    }
+}
@@ -0,0 +1,332 @@
+use crate::api1::{cmd, AsVueszApi1ValueStr, AutoName, PyNumber};
+use crate::page::{Alignment, AxisBinding, AxisDirection};
+use crate::style::ColorMap;
+use crate::CommandLineEmbeddingInterface;
+use std::io::Write;
+
+/// How the values between the minimum and maximum are mapped onto the colour map.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum ColorScaling {
+    Linear,
+    Sqrt,
+    Log,
+    Squared,
+}
+
+impl AsVueszApi1ValueStr for ColorScaling {
+    fn as_veusz_api1_value_str(&self) -> &str {
+        match self {
+            ColorScaling::Linear => "linear",
+            ColorScaling::Sqrt => "sqrt",
+            ColorScaling::Log => "log",
+            ColorScaling::Squared => "squared",
+        }
+    }
+}
+
+/// Shows a two dimensional dataset, colouring each cell by its value.
+pub struct Image {
+    name: AutoName<Self>,
+    axes: AxisBinding,
+    data: String,
+    color_map: Option<ColorMap>,
+    color_invert: Option<bool>,
+    min: Option<f64>,
+    max: Option<f64>,
+    scaling: Option<ColorScaling>,
+    smooth: Option<bool>,
+    transparency: Option<u8>,
+    transparent_data: Option<String>,
+}
+
+axis_binding!(Image);
+
+impl Image {
+    pub fn data(data: impl Into<String>) -> Self {
+        Self {
+            name: AutoName::default(),
+            axes: AxisBinding::default(),
+            data: data.into(),
+            color_map: None,
+            color_invert: None,
+            min: None,
+            max: None,
+            scaling: None,
+            smooth: None,
+            transparency: None,
+            transparent_data: None,
+        }
+    }
+
+    /// The name of the widget, as referred to by a [`ColorBar`].
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn set_color_map(&mut self, color_map: ColorMap) {
+        self.color_map = Some(color_map);
+    }
+
+    pub fn with_color_map(mut self, color_map: ColorMap) -> Self {
+        self.set_color_map(color_map);
+        self
+    }
+
+    pub fn set_color_invert(&mut self, invert: bool) {
+        self.color_invert = Some(invert);
+    }
+
+    pub fn with_color_invert(mut self, invert: bool) -> Self {
+        self.set_color_invert(invert);
+        self
+    }
+
+    /// Value mapped onto the start of the colour map, defaults to the minimum of the data.
+    pub fn set_min(&mut self, min: f64) {
+        self.min = Some(min);
+    }
+
+    pub fn with_min(mut self, min: f64) -> Self {
+        self.set_min(min);
+        self
+    }
+
+    /// Value mapped onto the end of the colour map, defaults to the maximum of the data.
+    pub fn set_max(&mut self, max: f64) {
+        self.max = Some(max);
+    }
+
+    pub fn with_max(mut self, max: f64) -> Self {
+        self.set_max(max);
+        self
+    }
+
+    pub fn set_scaling(&mut self, scaling: ColorScaling) {
+        self.scaling = Some(scaling);
+    }
+
+    pub fn with_scaling(mut self, scaling: ColorScaling) -> Self {
+        self.set_scaling(scaling);
+        self
+    }
+
+    /// Interpolate between the cells instead of drawing sharp edges.
+    pub fn set_smooth(&mut self, smooth: bool) {
+        self.smooth = Some(smooth);
+    }
+
+    pub fn with_smooth(mut self, smooth: bool) -> Self {
+        self.set_smooth(smooth);
+        self
+    }
+
+    /// Transparency of the whole image in percent, from 0 to 100, larger values are
+    /// clamped to 100.
+    pub fn set_transparency(&mut self, transparency: u8) {
+        self.transparency = Some(transparency.min(100));
+    }
+
+    pub fn with_transparency(mut self, transparency: u8) -> Self {
+        self.set_transparency(transparency);
+        self
+    }
+
+    /// Two dimensional dataset of the same shape with the transparency of each cell,
+    /// from 0 (opaque) to 1 (transparent).
+    pub fn set_transparent_data(&mut self, data: impl Into<String>) {
+        self.transparent_data = Some(data.into());
+    }
+
+    pub fn with_transparent_data(mut self, data: impl Into<String>) -> Self {
+        self.set_transparent_data(data);
+        self
+    }
+}
+
+impl CommandLineEmbeddingInterface for Image {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        cmd::Add("image", &self.name).write(writer)?;
+        cmd::ToUnique(&self.name).for_call(writer, |writer| {
+            cmd::Set("data", &self.data).write(writer)?;
+
+            if let Some(color_map) = &self.color_map {
+                cmd::Set("colorMap", color_map.as_veusz_api1_value_str()).write(writer)?;
+            }
+
+            if let Some(invert) = &self.color_invert {
+                cmd::SetRaw("colorInvert", invert.as_veusz_api1_value_str()).write(writer)?;
+            }
+
+            if let Some(min) = self.min {
+                cmd::SetRaw("min", PyNumber(min)).write(writer)?;
+            }
+
+            if let Some(max) = self.max {
+                cmd::SetRaw("max", PyNumber(max)).write(writer)?;
+            }
+
+            if let Some(scaling) = &self.scaling {
+                cmd::Set("colorScaling", scaling.as_veusz_api1_value_str()).write(writer)?;
+            }
+
+            if let Some(smooth) = &self.smooth {
+                cmd::SetRaw("smooth", smooth.as_veusz_api1_value_str()).write(writer)?;
+            }
+
+            if let Some(transparency) = self.transparency {
+                cmd::SetRaw("transparency", transparency).write(writer)?;
+            }
+
+            if let Some(data) = &self.transparent_data {
+                cmd::Set("transparentData", data).write(writer)?;
+            }
+
+            self.axes.write(writer)?;
+
+            Ok(())
+        })
+    }
+}
+
+/// Shows the colour map of an [`Image`] with the range of its values.
+pub struct ColorBar {
+    name: AutoName<Self>,
+    widget: String,
+    label: Option<String>,
+    direction: Option<AxisDirection>,
+    align_horizontal: Option<Alignment>,
+    align_vertical: Option<Alignment>,
+}
+
+impl ColorBar {
+    pub fn for_image(image: &Image) -> Self {
+        Self::for_widget(image.name())
+    }
+
+    /// For the image or other colour mapped widget of the given name.
+    pub fn for_widget(name: impl Into<String>) -> Self {
+        Self {
+            name: AutoName::default(),
+            widget: name.into(),
+            label: None,
+            direction: None,
+            align_horizontal: None,
+            align_vertical: None,
+        }
+    }
+
+    pub fn set_label(&mut self, label: impl Into<String>) {
+        self.label = Some(label.into());
+    }
+
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.set_label(label);
+        self
+    }
+
+    /// Colour bars are horizontal by default.
+    pub fn set_direction(&mut self, direction: AxisDirection) {
+        self.direction = Some(direction);
+    }
+
+    pub fn with_direction(mut self, direction: AxisDirection) -> Self {
+        self.set_direction(direction);
+        self
+    }
+
+    pub fn set_align_horizontal(&mut self, alignment: Alignment) {
+        self.align_horizontal = Some(alignment);
+    }
+
+    pub fn with_alignment_horizontal(mut self, alignment: Alignment) -> Self {
+        self.set_align_horizontal(alignment);
+        self
+    }
+
+    pub fn set_align_vertical(&mut self, alignment: Alignment) {
+        self.align_vertical = Some(alignment);
+    }
+
+    pub fn with_alignment_vertical(mut self, alignment: Alignment) -> Self {
+        self.set_align_vertical(alignment);
+        self
+    }
+}
+
+impl CommandLineEmbeddingInterface for ColorBar {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        cmd::Add("colorbar", &self.name).write(writer)?;
+        cmd::ToUnique(&self.name).for_call(writer, |writer| {
+            cmd::Set("widgetName", &self.widget).write(writer)?;
+
+            if let Some(label) = &self.label {
+                cmd::Set("label", label).write(writer)?;
+            }
+
+            if let Some(direction) = &self.direction {
+                cmd::Set("direction", direction.as_str()).write(writer)?;
+            }
+
+            if let Some(alignment) = &self.align_horizontal {
+                cmd::Set("horzPosn", alignment.as_str()).write(writer)?;
+            }
+
+            if let Some(alignment) = &self.align_vertical {
+                cmd::Set("vertPosn", alignment.as_str()).write(writer)?;
+            }
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api1::{written, PyStr};
+
+    #[test]
+    fn color_bar_refers_to_image() {
+        let image = Image::data("z")
+            .with_color_map(ColorMap::Heat)
+            .with_scaling(ColorScaling::Log)
+            .with_min(1.0);
+        let color_bar = ColorBar::for_image(&image)
+            .with_label("intensity")
+            .with_direction(AxisDirection::Vertical);
+        assert_eq!(
+            "Add(u'image', name=NAME, autoadd=False)\n\
+             To(NAME)\n\
+             Set(u'data', u'z')\n\
+             Set(u'colorMap', u'heat')\n\
+             Set(u'min', 1)\n\
+             Set(u'colorScaling', u'log')\n\
+             To('..')\n",
+            written(&image, image.name())
+        );
+        let image_name = PyStr(image.name()).to_string();
+        assert_eq!(
+            format!(
+                "Add(u'colorbar', name=NAME, autoadd=False)\n\
+                 To(NAME)\n\
+                 Set(u'widgetName', {image_name})\n\
+                 Set(u'label', u'intensity')\n\
+                 Set(u'direction', u'vertical')\n\
+                 To('..')\n"
+            ),
+            written(&color_bar, &color_bar.name)
+        );
+    }
+
+    #[test]
+    fn transparency_is_clamped() {
+        assert_eq!(
+            Some(100),
+            Image::data("z").with_transparency(150).transparency
+        );
+        assert_eq!(
+            Some(40),
+            Image::data("z").with_transparency(40).transparency
+        );
+    }
+}
@@ -0,0 +1,188 @@
+use crate::api1::{cmd, AutoName, PyNumber};
+use crate::page::{Alignment, TextConfig};
+use crate::style::fill::Fill;
+use crate::style::line::Line;
+use crate::CommandLineEmbeddingInterface;
+use std::io::Write;
+
+/// The legend of a graph, listing the widgets which have a key text.
+#[derive(Default)]
+pub struct Key {
+    name: AutoName<Self>,
+    title: Option<String>,
+    align_horizontal: Option<Alignment>,
+    align_vertical: Option<Alignment>,
+    manual_position: Option<(f64, f64)>,
+    columns: Option<u32>,
+    symbol_length: Option<f32>,
+    border: Option<Line>,
+    background: Option<Fill>,
+    text_config: Option<TextConfig>,
+}
+
+impl Key {
+    pub fn set_title(&mut self, title: impl Into<String>) {
+        self.title = Some(title.into());
+    }
+
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.set_title(title);
+        self
+    }
+
+    pub fn set_align_horizontal(&mut self, alignment: Alignment) {
+        self.align_horizontal = Some(alignment);
+    }
+
+    pub fn with_alignment_horizontal(mut self, alignment: Alignment) -> Self {
+        self.set_align_horizontal(alignment);
+        self
+    }
+
+    pub fn set_align_vertical(&mut self, alignment: Alignment) {
+        self.align_vertical = Some(alignment);
+    }
+
+    pub fn with_alignment_vertical(mut self, alignment: Alignment) -> Self {
+        self.set_align_vertical(alignment);
+        self
+    }
+
+    /// Places the key at the given fractions of the width and height of the graph,
+    /// ignoring the alignments.
+    pub fn set_manual_position(&mut self, x: f64, y: f64) {
+        self.manual_position = Some((x, y));
+    }
+
+    pub fn with_manual_position(mut self, x: f64, y: f64) -> Self {
+        self.set_manual_position(x, y);
+        self
+    }
+
+    pub fn set_columns(&mut self, columns: u32) {
+        self.columns = Some(columns);
+    }
+
+    pub fn with_columns(mut self, columns: u32) -> Self {
+        self.set_columns(columns);
+        self
+    }
+
+    /// Length of the line or symbol shown in front of each key text.
+    pub fn set_symbol_length(&mut self, pt: f32) {
+        self.symbol_length = Some(pt);
+    }
+
+    pub fn with_symbol_length(mut self, pt: f32) -> Self {
+        self.set_symbol_length(pt);
+        self
+    }
+
+    pub fn set_border(&mut self, border: Line) {
+        self.border = Some(border);
+    }
+
+    pub fn with_border(mut self, border: Line) -> Self {
+        self.set_border(border);
+        self
+    }
+
+    pub fn set_background(&mut self, background: Fill) {
+        self.background = Some(background);
+    }
+
+    pub fn with_background(mut self, background: Fill) -> Self {
+        self.set_background(background);
+        self
+    }
+
+    pub fn set_text_config(&mut self, text_config: impl Into<TextConfig>) {
+        self.text_config = Some(text_config.into());
+    }
+
+    pub fn with_text_config(mut self, text_config: impl Into<TextConfig>) -> Self {
+        self.set_text_config(text_config);
+        self
+    }
+}
+
+impl CommandLineEmbeddingInterface for Key {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        cmd::Add("key", &self.name).write(writer)?;
+        cmd::ToUnique(&self.name).for_call(writer, |writer| {
+            if let Some(title) = &self.title {
+                cmd::Set("title", title).write(writer)?;
+            }
+
+            match self.manual_position {
+                Some((x, y)) => {
+                    cmd::Set("horzPosn", "manual").write(writer)?;
+                    cmd::Set("vertPosn", "manual").write(writer)?;
+                    cmd::SetRaw("horzManual", PyNumber(x)).write(writer)?;
+                    cmd::SetRaw("vertManual", PyNumber(y)).write(writer)?;
+                }
+                None => {
+                    if let Some(alignment) = &self.align_horizontal {
+                        cmd::Set("horzPosn", alignment.as_str()).write(writer)?;
+                    }
+                    if let Some(alignment) = &self.align_vertical {
+                        cmd::Set("vertPosn", alignment.as_str()).write(writer)?;
+                    }
+                }
+            }
+
+            if let Some(columns) = self.columns {
+                cmd::SetRaw("columns", columns).write(writer)?;
+            }
+
+            if let Some(length) = self.symbol_length {
+                cmd::Set("keyLength", &format!("{length}pt")).write(writer)?;
+            }
+
+            if let Some(border) = &self.border {
+                border.write_as("Border", writer)?;
+            }
+
+            if let Some(background) = &self.background {
+                background.write_as("Background", writer)?;
+            }
+
+            if let Some(text_config) = &self.text_config {
+                text_config.write_as("Text", writer)?;
+            }
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api1::written;
+    use crate::page::TextSize;
+
+    #[test]
+    fn key_with_text_config() {
+        let key = Key::default()
+            .with_title("Series")
+            .with_alignment_horizontal(Alignment::Left)
+            .with_columns(2)
+            .with_text_config(
+                TextConfig::default()
+                    .with_size(TextSize::Pt(8.0))
+                    .with_font("Helvetica"),
+            );
+        assert_eq!(
+            "Add(u'key', name=NAME, autoadd=False)\n\
+             To(NAME)\n\
+             Set(u'title', u'Series')\n\
+             Set(u'horzPosn', u'left')\n\
+             Set(u'columns', 2)\n\
+             Set(u'Text/size', u'8pt')\n\
+             Set(u'Text/font', u'Helvetica')\n\
+             To('..')\n",
+            written(&key, &key.name)
+        );
+    }
+}
@@ -0,0 +1,1396 @@
+use crate::api1::{cmd, AsVueszApi1ValueStr, AutoName, PyList, PyNumber};
+use crate::page::bar::Bar;
+use crate::page::boxplot::BoxPlot;
+use crate::page::contour::Contour;
+use crate::page::fit::Fit;
+use crate::page::function::Function;
+use crate::page::image::{ColorBar, Image};
+use crate::page::key::Key;
+use crate::page::polar::Polar;
+use crate::page::scene3d::Scene3D;
+use crate::page::shape::{Ellipse, ImageFile, LineShape, Polygon, Rect};
+use crate::page::ternary::Ternary;
+use crate::page::vectorfield::VectorField;
+use crate::size::SizeUnit;
+use crate::style::line::Line;
+use crate::style::marker::{Marker, MarkerFill, MarkerLine};
+use crate::style::plot::PlotLine;
+use crate::style::{Color, ColorName};
+use crate::{CommandLineEmbeddingInterface, Error, Result};
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
+use std::io::Write;
+
+/// Implements the axis setters of the [`AxisBinding`] in the `axes` field.
+macro_rules! axis_binding {
+    ($plotter:ty) => {
+        impl $plotter {
+            /// Name of the horizontal axis of the graph, `x` by default.
+            pub fn set_x_axis(&mut self, name: impl Into<String>) {
+                self.axes.set_x(name);
+            }
+
+            pub fn with_x_axis(mut self, name: impl Into<String>) -> Self {
+                self.set_x_axis(name);
+                self
+            }
+
+            /// Name of the vertical axis of the graph, `y` by default.
+            pub fn set_y_axis(&mut self, name: impl Into<String>) {
+                self.axes.set_y(name);
+            }
+
+            pub fn with_y_axis(mut self, name: impl Into<String>) -> Self {
+                self.set_y_axis(name);
+                self
+            }
+
+            pub(crate) fn axes(&self) -> &$crate::page::AxisBinding {
+                &self.axes
+            }
+        }
+    };
+}
+
+pub mod bar;
+pub mod boxplot;
+pub mod contour;
+pub mod fit;
+pub mod function;
+pub mod image;
+pub mod key;
+pub mod nonorth;
+pub mod polar;
+pub mod scene3d;
+pub mod shape;
+pub mod ternary;
+pub mod vectorfield;
+
+#[derive(Default)]
+pub struct Page {
+    name: AutoName<Self>,
+    items: Vec<PageItem>,
+    width: Option<SizeUnit>,
+    height: Option<SizeUnit>,
+}
+
+impl Page {
+    /// The name of the widget, as first component of the paths of its children.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn add(&mut self, item: impl Into<PageItem>) {
+        self.items.push(item.into());
+    }
+
+    pub fn with_item(mut self, item: impl Into<PageItem>) -> Self {
+        self.add(item);
+        self
+    }
+
+    pub fn with_items(mut self, items: impl IntoIterator<Item = impl Into<PageItem>>) -> Self {
+        self.items.extend(items.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn with_width(mut self, width: impl Into<Option<SizeUnit>>) -> Self {
+        self.width = width.into();
+        self
+    }
+
+    pub fn with_height(mut self, height: impl Into<Option<SizeUnit>>) -> Self {
+        self.height = height.into();
+        self
+    }
+
+    /// Validates the graphs on this page, see [`Graph::validate`].
+    pub fn validate(&self) -> Result<()> {
+        self.items.iter().try_for_each(PageItem::validate)
+    }
+}
+
+impl CommandLineEmbeddingInterface for Page {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        cmd::Add("page", &self.name).write(writer)?;
+        cmd::ToUnique(&self.name).for_call(writer, |writer| {
+            for item in &self.items {
+                item.write(writer)?;
+            }
+            Ok(())
+        })?;
+
+        if let Some(width) = self.width {
+            cmd::Set("width", &width.to_string()).write(writer)?;
+        }
+
+        if let Some(height) = self.height {
+            cmd::Set("height", &height.to_string()).write(writer)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(derive_more::From)]
+pub enum PageItem {
+    Graph(Graph),
+    Grid(Grid),
+    Label(Label),
+    Polar(Polar),
+    Ternary(Ternary),
+    Scene3D(Scene3D),
+    Rect(Rect),
+    Ellipse(Ellipse),
+    LineShape(LineShape),
+    Polygon(Polygon),
+    ImageFile(ImageFile),
+}
+
+impl PageItem {
+    fn validate(&self) -> Result<()> {
+        match self {
+            PageItem::Graph(graph) => graph.validate(),
+            PageItem::Grid(grid) => grid.items.iter().try_for_each(PageItem::validate),
+            PageItem::Label(_)
+            | PageItem::Polar(_)
+            | PageItem::Ternary(_)
+            | PageItem::Scene3D(_)
+            | PageItem::Rect(_)
+            | PageItem::Ellipse(_)
+            | PageItem::LineShape(_)
+            | PageItem::Polygon(_)
+            | PageItem::ImageFile(_) => Ok(()),
+        }
+    }
+}
+
+impl CommandLineEmbeddingInterface for PageItem {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        match self {
+            PageItem::Graph(graph) => graph.write(writer),
+            PageItem::Grid(grid) => grid.write(writer),
+            PageItem::Label(label) => label.write(writer),
+            PageItem::Polar(polar) => polar.write(writer),
+            PageItem::Ternary(ternary) => ternary.write(writer),
+            PageItem::Scene3D(scene) => scene.write(writer),
+            PageItem::Rect(rect) => rect.write(writer),
+            PageItem::Ellipse(ellipse) => ellipse.write(writer),
+            PageItem::LineShape(line) => line.write(writer),
+            PageItem::Polygon(polygon) => polygon.write(writer),
+            PageItem::ImageFile(image) => image.write(writer),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Graph {
+    name: AutoName<Self>,
+    aspect: Option<AspectRatio>,
+    axes: Vec<Axis>,
+    items: Vec<GraphItem>,
+}
+
+impl Graph {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn set_aspect(&mut self, aspect: impl Into<AspectRatio>) {
+        self.aspect = Some(aspect.into());
+    }
+
+    pub fn with_aspect(mut self, aspect: impl Into<AspectRatio>) -> Self {
+        self.set_aspect(aspect);
+        self
+    }
+
+    pub fn add_axis(&mut self, axis: Axis) {
+        self.axes.push(axis);
+    }
+
+    pub fn with_xy_axis(mut self, x: impl Into<String>, y: impl Into<String>) -> Self {
+        self.add_axis(Axis::x(x));
+        self.add_axis(Axis::y(y));
+        self
+    }
+
+    pub fn with_axis(mut self, axis: Axis) -> Self {
+        self.add_axis(axis);
+        self
+    }
+
+    pub fn add(&mut self, item: impl Into<GraphItem>) {
+        self.items.push(item.into());
+    }
+
+    pub fn with_item(mut self, item: impl Into<GraphItem>) -> Self {
+        self.add(item);
+        self
+    }
+
+    pub fn with_items(mut self, items: impl IntoIterator<Item = impl Into<GraphItem>>) -> Self {
+        self.items.extend(items.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn add_xy(&mut self, xy: Xy) {
+        self.add(xy);
+    }
+
+    pub fn with_xy(mut self, xy: Xy) -> Self {
+        self.add_xy(xy);
+        self
+    }
+
+    pub fn with_xy_sets(self, sets: impl IntoIterator<Item = Xy>) -> Self {
+        self.with_items(sets)
+    }
+
+    /// Checks that every axis the items are bound to is added to this graph, and that
+    /// x axes are horizontal and y axes vertical.
+    pub fn validate(&self) -> Result<()> {
+        for item in &self.items {
+            let Some(binding) = item.axis_binding() else {
+                continue;
+            };
+            for (name, expected) in binding.names() {
+                let Some(axis) = self.axes.iter().find(|axis| axis.name == name) else {
+                    return Err(Error::Invalid(format!(
+                        "graph {} has no axis {name}",
+                        &*self.name
+                    )));
+                };
+                let direction = axis.direction.unwrap_or(AxisDirection::Horizontal);
+                if direction != expected {
+                    return Err(Error::Invalid(format!(
+                        "axis {name} of graph {} is {} but bound as {} axis",
+                        &*self.name,
+                        direction.as_str(),
+                        expected.as_str()
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl CommandLineEmbeddingInterface for Graph {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        cmd::Add("graph", &self.name).write(writer)?;
+        cmd::ToUnique(&self.name).for_call(writer, |writer| {
+            if let Some(aspect) = self.aspect {
+                match aspect {
+                    AspectRatio::Auto => cmd::Set("aspect", "Auto").write(writer)?,
+                    AspectRatio::Fix(value) => {
+                        cmd::SetRaw("aspect", PyNumber(value)).write(writer)?
+                    }
+                }
+            }
+            for axis in &self.axes {
+                axis.write(writer)?;
+            }
+            for item in &self.items {
+                item.write(writer)?;
+            }
+            Ok(())
+        })
+    }
+}
+
+/// The plotters and other widgets within a [`Graph`].
+#[derive(derive_more::From)]
+pub enum GraphItem {
+    Xy(Xy),
+    Function(Function),
+    Bar(Bar),
+    BoxPlot(BoxPlot),
+    Image(Image),
+    ColorBar(ColorBar),
+    Contour(Contour),
+    VectorField(VectorField),
+    Fit(Fit),
+    Key(Key),
+    Rect(Rect),
+    Ellipse(Ellipse),
+    LineShape(LineShape),
+    Polygon(Polygon),
+    ImageFile(ImageFile),
+}
+
+impl CommandLineEmbeddingInterface for GraphItem {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        match self {
+            GraphItem::Xy(xy) => xy.write(writer),
+            GraphItem::Function(function) => function.write(writer),
+            GraphItem::Bar(bar) => bar.write(writer),
+            GraphItem::BoxPlot(boxplot) => boxplot.write(writer),
+            GraphItem::Image(image) => image.write(writer),
+            GraphItem::ColorBar(color_bar) => color_bar.write(writer),
+            GraphItem::Contour(contour) => contour.write(writer),
+            GraphItem::VectorField(field) => field.write(writer),
+            GraphItem::Fit(fit) => fit.write(writer),
+            GraphItem::Key(key) => key.write(writer),
+            GraphItem::Rect(rect) => rect.write(writer),
+            GraphItem::Ellipse(ellipse) => ellipse.write(writer),
+            GraphItem::LineShape(line) => line.write(writer),
+            GraphItem::Polygon(polygon) => polygon.write(writer),
+            GraphItem::ImageFile(image) => image.write(writer),
+        }
+    }
+}
+
+impl GraphItem {
+    fn axis_binding(&self) -> Option<&AxisBinding> {
+        match self {
+            GraphItem::Xy(xy) => Some(xy.axes()),
+            GraphItem::Function(function) => Some(function.axes()),
+            GraphItem::Bar(bar) => Some(bar.axes()),
+            GraphItem::BoxPlot(boxplot) => Some(boxplot.axes()),
+            GraphItem::Image(image) => Some(image.axes()),
+            GraphItem::Contour(contour) => Some(contour.axes()),
+            GraphItem::VectorField(field) => Some(field.axes()),
+            GraphItem::Fit(fit) => Some(fit.axes()),
+            GraphItem::ColorBar(_)
+            | GraphItem::Key(_)
+            | GraphItem::Rect(_)
+            | GraphItem::Ellipse(_)
+            | GraphItem::LineShape(_)
+            | GraphItem::Polygon(_)
+            | GraphItem::ImageFile(_) => None,
+        }
+    }
+}
+
+/// The axes a plotter is drawn against, `x` and `y` of its graph unless set otherwise.
+#[derive(Default)]
+pub(crate) struct AxisBinding {
+    x: Option<String>,
+    y: Option<String>,
+}
+
+impl AxisBinding {
+    pub(crate) fn set_x(&mut self, name: impl Into<String>) {
+        self.x = Some(name.into());
+    }
+
+    pub(crate) fn set_y(&mut self, name: impl Into<String>) {
+        self.y = Some(name.into());
+    }
+
+    /// The bound axes and the direction they need, veusz binds to `x` and `y` by default.
+    fn names(&self) -> [(&str, AxisDirection); 2] {
+        [
+            (self.x.as_deref().unwrap_or("x"), AxisDirection::Horizontal),
+            (self.y.as_deref().unwrap_or("y"), AxisDirection::Vertical),
+        ]
+    }
+
+    pub(crate) fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        if let Some(x) = &self.x {
+            cmd::Set("xAxis", x).write(writer)?;
+        }
+
+        if let Some(y) = &self.y {
+            cmd::Set("yAxis", y).write(writer)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(derive_more::From, Copy, Clone, PartialEq)]
+pub enum AspectRatio {
+    Auto,
+    Fix(f64),
+}
+
+#[derive(Default)]
+pub struct Grid {
+    name: AutoName<Self>,
+    rows: Option<u32>,
+    columns: Option<u32>,
+    items: Vec<PageItem>,
+}
+
+impl Grid {
+    pub fn set_rows(&mut self, rows: u32) {
+        self.rows = Some(rows);
+    }
+
+    pub fn with_rows(mut self, rows: u32) -> Self {
+        self.set_rows(rows);
+        self
+    }
+
+    pub fn set_columns(&mut self, columns: u32) {
+        self.columns = Some(columns);
+    }
+
+    pub fn with_columns(mut self, columns: u32) -> Self {
+        self.set_columns(columns);
+        self
+    }
+
+    pub fn add(&mut self, item: impl Into<PageItem>) {
+        self.items.push(item.into());
+    }
+
+    pub fn with(mut self, item: impl Into<PageItem>) -> Self {
+        self.add(item);
+        self
+    }
+
+    pub fn with_items(mut self, items: impl IntoIterator<Item = impl Into<PageItem>>) -> Self {
+        self.items.extend(items.into_iter().map(Into::into));
+        self
+    }
+}
+
+impl CommandLineEmbeddingInterface for Grid {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        cmd::Add("grid", &self.name).write(writer)?;
+        cmd::ToUnique(&self.name).for_call(writer, |writer| {
+            if let Some(rows) = self.rows {
+                cmd::SetRaw("rows", rows).write(writer)?;
+            }
+            if let Some(columns) = self.columns {
+                cmd::SetRaw("columns", columns).write(writer)?;
+            }
+            for item in &self.items {
+                item.write(writer)?;
+            }
+            Ok(())
+        })
+    }
+}
+
+#[derive(Copy, Clone, PartialOrd, PartialEq)]
+pub enum AxisDirection {
+    Vertical,
+    Horizontal,
+}
+
+impl AxisDirection {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            AxisDirection::Vertical => "vertical",
+            AxisDirection::Horizontal => "horizontal",
+        }
+    }
+}
+
+/// How an [`Axis`] extends its range beyond the data when it has no explicit minimum
+/// or maximum.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum AutoRange {
+    /// The range of the data
+    Exact,
+    /// Up to the next major tick
+    NextTick,
+    /// The range of the data plus 2%
+    Plus2,
+    /// The range of the data plus 5%
+    Plus5,
+    /// The range of the data plus 10%
+    Plus10,
+    /// The range of the data plus 15%
+    Plus15,
+}
+
+impl AsVueszApi1ValueStr for AutoRange {
+    fn as_veusz_api1_value_str(&self) -> &str {
+        match self {
+            AutoRange::Exact => "exact",
+            AutoRange::NextTick => "next-tick",
+            AutoRange::Plus2 => "+2%",
+            AutoRange::Plus5 => "+5%",
+            AutoRange::Plus10 => "+10%",
+            AutoRange::Plus15 => "+15%",
+        }
+    }
+}
+
+/// The major or minor ticks of an [`Axis`].
+#[derive(Default)]
+pub struct Ticks {
+    number: Option<u32>,
+    length: Option<f32>,
+    line: Option<Line>,
+}
+
+impl Ticks {
+    /// Approximate number of ticks, veusz picks round values near it.
+    pub fn set_number(&mut self, number: u32) {
+        self.number = Some(number);
+    }
+
+    pub fn with_number(mut self, number: u32) -> Self {
+        self.set_number(number);
+        self
+    }
+
+    pub fn set_length(&mut self, pt: f32) {
+        self.length = Some(pt);
+    }
+
+    pub fn with_length(mut self, pt: f32) -> Self {
+        self.set_length(pt);
+        self
+    }
+
+    pub fn set_line(&mut self, line: Line) {
+        self.line = Some(line);
+    }
+
+    pub fn with_line(mut self, line: Line) -> Self {
+        self.set_line(line);
+        self
+    }
+
+    fn write_as<W: Write>(&self, group: &str, writer: &mut W) -> std::io::Result<()> {
+        if let Some(number) = self.number {
+            cmd::SetRaw(&format!("{group}/number"), number).write(writer)?;
+        }
+
+        if let Some(length) = self.length {
+            cmd::Set(&format!("{group}/length"), &format!("{length}pt")).write(writer)?;
+        }
+
+        if let Some(line) = &self.line {
+            line.write_as(group, writer)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The labels of the major ticks of an [`Axis`].
+#[derive(Default)]
+pub struct TickLabels {
+    format: Option<String>,
+    rotation: Option<u32>,
+    text_config: Option<TextConfig>,
+    hide: Option<bool>,
+}
+
+impl TickLabels {
+    /// Format of the values, like `%.2f`, `%Vg` or `%VDd.%VDm.` for dates.
+    pub fn set_format(&mut self, format: impl Into<String>) {
+        self.format = Some(format.into());
+    }
+
+    pub fn with_format(mut self, format: impl Into<String>) -> Self {
+        self.set_format(format);
+        self
+    }
+
+    /// Rotation in degrees, veusz only accepts multiples of 45.
+    pub fn set_rotation(&mut self, degrees: u32) {
+        self.rotation = Some(degrees);
+    }
+
+    pub fn with_rotation(mut self, degrees: u32) -> Self {
+        self.set_rotation(degrees);
+        self
+    }
+
+    pub fn set_text_config(&mut self, text_config: impl Into<TextConfig>) {
+        self.text_config = Some(text_config.into());
+    }
+
+    pub fn with_text_config(mut self, text_config: impl Into<TextConfig>) -> Self {
+        self.set_text_config(text_config);
+        self
+    }
+
+    pub fn set_hide(&mut self, hide: bool) {
+        self.hide = Some(hide);
+    }
+
+    pub fn with_hide(mut self, hide: bool) -> Self {
+        self.set_hide(hide);
+        self
+    }
+
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        if let Some(format) = &self.format {
+            cmd::Set("TickLabels/format", format).write(writer)?;
+        }
+
+        if let Some(rotation) = self.rotation {
+            cmd::Set("TickLabels/rotate", &rotation.to_string()).write(writer)?;
+        }
+
+        if let Some(text_config) = &self.text_config {
+            text_config.write_as("TickLabels", writer)?;
+        }
+
+        if let Some(hide) = &self.hide {
+            cmd::SetRaw("TickLabels/hide", hide.as_veusz_api1_value_str()).write(writer)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The side of the graph an [`Axis`] is drawn at.
+#[derive(Copy, Clone, PartialEq)]
+pub enum AxisPlacement {
+    Left,
+    Right,
+    Top,
+    Bottom,
+    /// At the given fraction of the width or height of the graph, from 0 to 1, keeping
+    /// the direction of the axis
+    Other(f64),
+}
+
+pub struct Axis {
+    name: String,
+    label: String,
+    direction: Option<AxisDirection>,
+    other_position: Option<f64>,
+    min: Option<f64>,
+    max: Option<f64>,
+    log: Option<bool>,
+    auto_range: Option<AutoRange>,
+    reflect: Option<bool>,
+    lower_position: Option<f64>,
+    upper_position: Option<f64>,
+    match_axis: Option<String>,
+    major_ticks: Option<Ticks>,
+    minor_ticks: Option<Ticks>,
+    grid_lines: Option<Line>,
+    minor_grid_lines: Option<Line>,
+    tick_labels: Option<TickLabels>,
+}
+
+impl Axis {
+    pub fn x(label: impl Into<String>) -> Self {
+        Self::new("x", label.into(), None)
+    }
+
+    pub fn y(label: impl Into<String>) -> Self {
+        Self::new("y", label.into(), Some(AxisDirection::Vertical))
+    }
+
+    /// An additional axis, like a second y axis, which plotters are bound to by its name.
+    /// It is horizontal unless placed otherwise.
+    pub fn named(name: impl Into<String>, label: impl Into<String>) -> Self {
+        Self::new(name, label.into(), None)
+    }
+
+    fn new(name: impl Into<String>, label: String, direction: Option<AxisDirection>) -> Self {
+        Self {
+            name: name.into(),
+            label,
+            direction,
+            other_position: None,
+            min: None,
+            max: None,
+            log: None,
+            auto_range: None,
+            reflect: None,
+            lower_position: None,
+            upper_position: None,
+            match_axis: None,
+            major_ticks: None,
+            minor_ticks: None,
+            grid_lines: None,
+            minor_grid_lines: None,
+            tick_labels: None,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn set_direction(&mut self, direction: AxisDirection) {
+        self.direction = Some(direction);
+    }
+
+    pub fn with_direction(mut self, direction: AxisDirection) -> Self {
+        self.set_direction(direction);
+        self
+    }
+
+    /// Places the axis at a side of the graph, which also sets its direction.
+    pub fn set_placement(&mut self, placement: AxisPlacement) {
+        let (direction, position) = match placement {
+            AxisPlacement::Left => (Some(AxisDirection::Vertical), 0.0),
+            AxisPlacement::Right => (Some(AxisDirection::Vertical), 1.0),
+            AxisPlacement::Bottom => (Some(AxisDirection::Horizontal), 0.0),
+            AxisPlacement::Top => (Some(AxisDirection::Horizontal), 1.0),
+            AxisPlacement::Other(position) => (None, position),
+        };
+        if direction.is_some() {
+            self.direction = direction;
+        }
+        self.other_position = Some(position);
+    }
+
+    pub fn with_placement(mut self, placement: AxisPlacement) -> Self {
+        self.set_placement(placement);
+        self
+    }
+
+    pub fn with_min(mut self, min: impl Into<Option<f64>>) -> Self {
+        self.min = min.into();
+        self
+    }
+
+    pub fn with_max(mut self, max: impl Into<Option<f64>>) -> Self {
+        self.max = max.into();
+        self
+    }
+
+    /// Logarithmic instead of linear scale.
+    pub fn set_log(&mut self, log: bool) {
+        self.log = Some(log);
+    }
+
+    pub fn with_log(mut self, log: bool) -> Self {
+        self.set_log(log);
+        self
+    }
+
+    pub fn set_auto_range(&mut self, auto_range: AutoRange) {
+        self.auto_range = Some(auto_range);
+    }
+
+    pub fn with_auto_range(mut self, auto_range: AutoRange) -> Self {
+        self.set_auto_range(auto_range);
+        self
+    }
+
+    /// Inverts the axis, so that values decrease along its direction.
+    pub fn set_reflect(&mut self, reflect: bool) {
+        self.reflect = Some(reflect);
+    }
+
+    pub fn with_reflect(mut self, reflect: bool) -> Self {
+        self.set_reflect(reflect);
+        self
+    }
+
+    /// Start of the axis as fraction of the width or height of the graph, from 0 to 1.
+    pub fn set_lower_position(&mut self, fraction: f64) {
+        self.lower_position = Some(fraction);
+    }
+
+    pub fn with_lower_position(mut self, fraction: f64) -> Self {
+        self.set_lower_position(fraction);
+        self
+    }
+
+    /// End of the axis as fraction of the width or height of the graph, from 0 to 1.
+    pub fn set_upper_position(&mut self, fraction: f64) {
+        self.upper_position = Some(fraction);
+    }
+
+    pub fn with_upper_position(mut self, fraction: f64) -> Self {
+        self.set_upper_position(fraction);
+        self
+    }
+
+    /// Path of another axis to copy the range from, like `../../graph1/x`.
+    pub fn set_match_axis(&mut self, path: impl Into<String>) {
+        self.match_axis = Some(path.into());
+    }
+
+    pub fn with_match_axis(mut self, path: impl Into<String>) -> Self {
+        self.set_match_axis(path);
+        self
+    }
+
+    pub fn set_major_ticks(&mut self, ticks: Ticks) {
+        self.major_ticks = Some(ticks);
+    }
+
+    pub fn with_major_ticks(mut self, ticks: Ticks) -> Self {
+        self.set_major_ticks(ticks);
+        self
+    }
+
+    pub fn set_minor_ticks(&mut self, ticks: Ticks) {
+        self.minor_ticks = Some(ticks);
+    }
+
+    pub fn with_minor_ticks(mut self, ticks: Ticks) -> Self {
+        self.set_minor_ticks(ticks);
+        self
+    }
+
+    /// Shows lines across the graph at the major ticks.
+    pub fn set_grid_lines(&mut self, line: Line) {
+        self.grid_lines = Some(line);
+    }
+
+    pub fn with_grid_lines(mut self, line: Line) -> Self {
+        self.set_grid_lines(line);
+        self
+    }
+
+    /// Shows lines across the graph at the minor ticks.
+    pub fn set_minor_grid_lines(&mut self, line: Line) {
+        self.minor_grid_lines = Some(line);
+    }
+
+    pub fn with_minor_grid_lines(mut self, line: Line) -> Self {
+        self.set_minor_grid_lines(line);
+        self
+    }
+
+    pub fn set_tick_labels(&mut self, tick_labels: TickLabels) {
+        self.tick_labels = Some(tick_labels);
+    }
+
+    pub fn with_tick_labels(mut self, tick_labels: TickLabels) -> Self {
+        self.set_tick_labels(tick_labels);
+        self
+    }
+}
+
+impl CommandLineEmbeddingInterface for Axis {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        cmd::Add("axis", &self.name).write(writer)?;
+        cmd::ToUnique(&self.name).for_call(writer, |writer| {
+            cmd::Set("label", &self.label).write(writer)?;
+
+            if let Some(direction) = &self.direction {
+                cmd::Set("direction", direction.as_str()).write(writer)?;
+            }
+
+            if let Some(position) = self.other_position {
+                cmd::SetRaw("otherPosition", PyNumber(position)).write(writer)?;
+            }
+
+            if let Some(min) = self.min {
+                cmd::SetRaw("min", PyNumber(min)).write(writer)?;
+            }
+
+            if let Some(max) = self.max {
+                cmd::SetRaw("max", PyNumber(max)).write(writer)?;
+            }
+
+            if let Some(log) = &self.log {
+                cmd::SetRaw("log", log.as_veusz_api1_value_str()).write(writer)?;
+            }
+
+            if let Some(auto_range) = &self.auto_range {
+                cmd::Set("autoRange", auto_range.as_veusz_api1_value_str()).write(writer)?;
+            }
+
+            if let Some(reflect) = &self.reflect {
+                cmd::SetRaw("reflect", reflect.as_veusz_api1_value_str()).write(writer)?;
+            }
+
+            if let Some(position) = self.lower_position {
+                cmd::SetRaw("lowerPosition", PyNumber(position)).write(writer)?;
+            }
+
+            if let Some(position) = self.upper_position {
+                cmd::SetRaw("upperPosition", PyNumber(position)).write(writer)?;
+            }
+
+            if let Some(path) = &self.match_axis {
+                cmd::Set("match", path).write(writer)?;
+            }
+
+            if let Some(ticks) = &self.major_ticks {
+                ticks.write_as("MajorTicks", writer)?;
+            }
+
+            if let Some(ticks) = &self.minor_ticks {
+                ticks.write_as("MinorTicks", writer)?;
+            }
+
+            for (group, line) in [
+                ("GridLines", &self.grid_lines),
+                ("MinorGridLines", &self.minor_grid_lines),
+            ] {
+                if let Some(line) = line {
+                    // hidden by default, the line may still hide them explicitly
+                    cmd::SetRaw(&format!("{group}/hide"), false.as_veusz_api1_value_str())
+                        .write(writer)?;
+                    line.write_as(group, writer)?;
+                }
+            }
+
+            if let Some(tick_labels) = &self.tick_labels {
+                tick_labels.write(writer)?;
+            }
+
+            Ok(())
+        })
+    }
+}
+
+pub struct Xy {
+    name: AutoName<Self>,
+    color: Option<Color>,
+    marker: Option<Marker>,
+    marker_line: Option<MarkerLine>,
+    marker_fill: Option<MarkerFill>,
+    plot_line: Option<PlotLine>,
+    key_text: Option<String>,
+    axes: AxisBinding,
+    x_data: String,
+    y_data: String,
+}
+
+axis_binding!(Xy);
+
+impl Xy {
+    pub fn data(x_data: impl Into<String>, y_data: impl Into<String>) -> Self {
+        Self {
+            name: AutoName::default(),
+            color: None,
+            marker: None,
+            marker_line: None,
+            marker_fill: None,
+            plot_line: None,
+            key_text: None,
+            axes: AxisBinding::default(),
+            x_data: x_data.into(),
+            y_data: y_data.into(),
+        }
+    }
+
+    pub fn set_color(&mut self, color: Color) {
+        self.color = Some(color);
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.set_color(color);
+        self
+    }
+
+    pub fn set_marker(&mut self, marker: Marker) {
+        self.marker = Some(marker);
+    }
+
+    pub fn with_marker(mut self, marker: Marker) -> Self {
+        self.set_marker(marker);
+        self
+    }
+
+    pub fn set_marker_line(&mut self, marker_line: MarkerLine) {
+        self.marker_line = Some(marker_line);
+    }
+
+    pub fn with_marker_line(mut self, marker_line: MarkerLine) -> Self {
+        self.set_marker_line(marker_line);
+        self
+    }
+
+    pub fn set_marker_fill(&mut self, marker_fill: MarkerFill) {
+        self.marker_fill = Some(marker_fill);
+    }
+
+    pub fn with_marker_fill(mut self, marker_fill: MarkerFill) -> Self {
+        self.set_marker_fill(marker_fill);
+        self
+    }
+
+    pub fn set_plot_line(&mut self, plot_line: PlotLine) {
+        self.plot_line = Some(plot_line);
+    }
+
+    pub fn with_plot_line(mut self, plot_line: PlotLine) -> Self {
+        self.set_plot_line(plot_line);
+        self
+    }
+
+    /// Text shown for this series in the key of the graph.
+    pub fn set_key_text(&mut self, text: impl Into<String>) {
+        self.key_text = Some(text.into());
+    }
+
+    pub fn with_key_text(mut self, text: impl Into<String>) -> Self {
+        self.set_key_text(text);
+        self
+    }
+}
+
+impl CommandLineEmbeddingInterface for Xy {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        cmd::Add("xy", &self.name).write(writer)?;
+        cmd::ToUnique(&self.name).for_call(writer, |writer| {
+            if let Some(marker) = &self.marker {
+                cmd::Set("marker", marker.as_veusz_api1_value_str()).write(writer)?;
+            }
+
+            if let Some(color) = &self.color {
+                color.write(writer)?;
+            }
+
+            if let Some(marker_line) = &self.marker_line {
+                marker_line.write(writer)?;
+            }
+
+            if let Some(marker_fill) = &self.marker_fill {
+                marker_fill.write(writer)?;
+            }
+
+            if let Some(plot_line) = &self.plot_line {
+                plot_line.write(writer)?;
+            }
+
+            if let Some(text) = &self.key_text {
+                cmd::Set("key", text).write(writer)?;
+            }
+
+            cmd::Set("xData", &self.x_data).write(writer)?;
+            cmd::Set("yData", &self.y_data).write(writer)?;
+
+            self.axes.write(writer)?;
+
+            Ok(())
+        })
+    }
+}
+
+pub struct Label {
+    name: AutoName<Self>,
+    text: Cow<'static, str>,
+    x_positions: Vec<f64>,
+    y_positions: Vec<f64>,
+    align_horizontal: Option<Alignment>,
+    align_vertical: Option<Alignment>,
+    positioning: Option<Positioning>,
+    text_config: Option<TextConfig>,
+    // config: LabelConfig, // TODO
+}
+
+impl Label {
+    pub fn set_x_positions(&mut self, positions: impl Into<Vec<f64>>) {
+        self.x_positions = positions.into();
+    }
+
+    pub fn with_x_positions(mut self, positions: impl Into<Vec<f64>>) -> Self {
+        self.set_x_positions(positions);
+        self
+    }
+
+    pub fn set_y_positions(&mut self, positions: impl Into<Vec<f64>>) {
+        self.y_positions = positions.into();
+    }
+
+    pub fn with_y_positions(mut self, positions: impl Into<Vec<f64>>) -> Self {
+        self.set_y_positions(positions);
+        self
+    }
+
+    pub fn set_align_horizontal(&mut self, alignment: Alignment) {
+        self.align_horizontal = Some(alignment);
+    }
+
+    pub fn with_alignment_horizontal(mut self, alignemnt: Alignment) -> Self {
+        self.set_align_horizontal(alignemnt);
+        self
+    }
+
+    pub fn set_align_vertical(&mut self, alignment: Alignment) {
+        self.align_vertical = Some(alignment);
+    }
+
+    pub fn with_alignment_vertical(mut self, alignment: Alignment) -> Self {
+        self.set_align_vertical(alignment);
+        self
+    }
+
+    pub fn set_text_config(&mut self, text_config: impl Into<TextConfig>) {
+        self.text_config = Some(text_config.into());
+    }
+
+    pub fn with_text_config(mut self, text_config: impl Into<TextConfig>) -> Self {
+        self.set_text_config(text_config);
+        self
+    }
+
+    pub fn set_positioning(&mut self, positioning: impl Into<Positioning>) {
+        self.positioning = Some(positioning.into());
+    }
+
+    pub fn with_positioning(mut self, positioning: impl Into<Positioning>) -> Self {
+        self.set_positioning(positioning);
+        self
+    }
+}
+
+impl<I: Into<Cow<'static, str>>> From<I> for Label {
+    fn from(value: I) -> Self {
+        Self {
+            name: Default::default(),
+            text: value.into(),
+            x_positions: Vec::default(),
+            y_positions: Vec::default(),
+            align_horizontal: None,
+            align_vertical: None,
+            positioning: None,
+            text_config: None,
+        }
+    }
+}
+
+impl CommandLineEmbeddingInterface for Label {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        cmd::Add("label", &self.name).write(writer)?;
+        cmd::ToUnique(&self.name).for_call(writer, |writer| {
+            cmd::Set("label", &self.text).write(writer)?;
+            if !self.x_positions.is_empty() {
+                let x_positions = PyList(self.x_positions.iter().map(PyNumber));
+                cmd::SetData("xPos", &x_positions.to_string()).write(writer)?;
+            }
+            if !self.y_positions.is_empty() {
+                let y_positions = PyList(self.y_positions.iter().map(PyNumber));
+                cmd::SetData("yPos", &y_positions.to_string()).write(writer)?;
+            }
+            if let Some(alignment) = &self.align_horizontal {
+                cmd::Set("alignHorz", alignment.as_str()).write(writer)?;
+            }
+            if let Some(alignment) = &self.align_vertical {
+                cmd::Set("alignVert", alignment.as_str()).write(writer)?;
+            }
+            if let Some(positioning) = &self.positioning {
+                cmd::Set("positioning", positioning.as_str()).write(writer)?;
+            }
+            if let Some(text_config) = &self.text_config {
+                text_config.write_as("Text", writer)?;
+            }
+            Ok(())
+        })
+    }
+}
+
+#[derive(Default)]
+pub struct TextConfig {
+    size: Option<TextSize>,
+    font: Option<String>,
+    color: Option<String>,
+}
+
+impl TextConfig {
+    pub fn set_size(&mut self, size: impl Into<TextSize>) {
+        self.size = Some(size.into());
+    }
+
+    pub fn with_size(mut self, size: impl Into<TextSize>) -> Self {
+        self.set_size(size);
+        self
+    }
+
+    /// Name of the font family, like `Helvetica`.
+    pub fn set_font(&mut self, font: impl Into<String>) {
+        self.font = Some(font.into());
+    }
+
+    pub fn with_font(mut self, font: impl Into<String>) -> Self {
+        self.set_font(font);
+        self
+    }
+
+    pub fn set_color(&mut self, color: impl Into<String>) {
+        self.color = Some(color.into());
+    }
+
+    pub fn with_color(mut self, color: impl Into<String>) -> Self {
+        self.set_color(color);
+        self
+    }
+
+    pub fn set_color_by_name(&mut self, color_name: ColorName) {
+        self.color = Some(color_name.as_veusz_api1_value_str().to_string());
+    }
+
+    pub fn with_color_by_name(mut self, color_name: ColorName) -> Self {
+        self.set_color_by_name(color_name);
+        self
+    }
+
+    /// Writes the settings below the given settings group, like `Text`.
+    pub(crate) fn write_as<W: Write>(&self, group: &str, writer: &mut W) -> std::io::Result<()> {
+        if let Some(size) = &self.size {
+            cmd::Set(&format!("{group}/size"), &size.to_string()).write(writer)?;
+        }
+
+        if let Some(font) = &self.font {
+            cmd::Set(&format!("{group}/font"), font).write(writer)?;
+        }
+
+        if let Some(color) = &self.color {
+            cmd::Set(&format!("{group}/color"), color).write(writer)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: Into<TextSize>> From<T> for TextConfig {
+    fn from(value: T) -> Self {
+        Self::default().with_size(value)
+    }
+}
+
+pub enum TextSize {
+    Pt(f64),
+}
+
+impl Display for TextSize {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TextSize::Pt(pt) => write!(f, "{pt}pt"),
+        }
+    }
+}
+
+pub enum Alignment {
+    Top,
+    Bottom,
+    Left,
+    Right,
+    Center,
+}
+
+impl Alignment {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Alignment::Top => "top",
+            Alignment::Bottom => "bottom",
+            Alignment::Left => "left",
+            Alignment::Right => "right",
+            Alignment::Center => "centre",
+        }
+    }
+}
+
+pub enum Positioning {
+    Relative,
+    Axes,
+}
+
+impl Positioning {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Positioning::Relative => "relative",
+            Positioning::Axes => "axes",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api1::written;
+    use crate::style::line::LineStyle;
+
+    #[test]
+    fn log_axis_with_grid_and_tick_labels() {
+        let axis = Axis::y("current")
+            .with_log(true)
+            .with_auto_range(AutoRange::NextTick)
+            .with_reflect(true)
+            .with_match_axis("../../other/y")
+            .with_major_ticks(Ticks::default().with_number(4).with_length(8.0))
+            .with_grid_lines(Line::default().with_style(LineStyle::Dotted))
+            .with_tick_labels(
+                TickLabels::default()
+                    .with_format("%.1e")
+                    .with_rotation(45)
+                    .with_text_config(TextSize::Pt(9.0)),
+            );
+        assert_eq!(
+            "Add(u'axis', name=u'y', autoadd=False)\n\
+             To(u'y')\n\
+             Set(u'label', u'current')\n\
+             Set(u'direction', u'vertical')\n\
+             Set(u'log', True)\n\
+             Set(u'autoRange', u'next-tick')\n\
+             Set(u'reflect', True)\n\
+             Set(u'match', u'../../other/y')\n\
+             Set(u'MajorTicks/number', 4)\n\
+             Set(u'MajorTicks/length', u'8pt')\n\
+             Set(u'GridLines/hide', False)\n\
+             Set(u'GridLines/style', u'dotted')\n\
+             Set(u'TickLabels/format', u'%.1e')\n\
+             Set(u'TickLabels/rotate', u'45')\n\
+             Set(u'TickLabels/size', u'9pt')\n\
+             To('..')\n",
+            written(&axis, "")
+        );
+    }
+
+    #[test]
+    fn non_finite_axis_range() {
+        let axis = Axis::x("time")
+            .with_min(f64::NEG_INFINITY)
+            .with_max(f64::NAN);
+        let output = written(&axis, "");
+        assert!(output.contains("Set(u'min', float('-inf'))\n"));
+        assert!(output.contains("Set(u'max', float('nan'))\n"));
+    }
+
+    #[test]
+    fn xy_on_second_y_axis() {
+        let xy = Xy::data("time", "pressure").with_y_axis("y2");
+        let xy_name = String::from(&*xy.name);
+        let graph = Graph::default()
+            .with_xy_axis("time", "temperature")
+            .with_axis(Axis::named("y2", "pressure").with_placement(AxisPlacement::Right))
+            .with_xy(xy);
+        let output = written(&graph, &xy_name);
+        assert!(output.contains(
+            "Add(u'axis', name=u'y2', autoadd=False)\n\
+             To(u'y2')\n\
+             Set(u'label', u'pressure')\n\
+             Set(u'direction', u'vertical')\n\
+             Set(u'otherPosition', 1)\n\
+             To('..')\n"
+        ));
+        assert!(output.contains(
+            "To(NAME)\n\
+             Set(u'xData', u'time')\n\
+             Set(u'yData', u'pressure')\n\
+             Set(u'yAxis', u'y2')\n\
+             To('..')\n"
+        ));
+    }
+
+    #[test]
+    fn unknown_axis_is_invalid() {
+        let graph = Graph::default()
+            .with_xy_axis("x", "y")
+            .with_item(Function::expression("x").with_x_axis("x2"));
+        assert!(matches!(graph.validate(), Err(Error::Invalid(_))));
+    }
+
+    #[test]
+    fn axis_of_wrong_direction_is_invalid() {
+        let graph = Graph::default()
+            .with_xy_axis("x", "y")
+            .with_axis(Axis::named("x2", "time"))
+            .with_item(Function::expression("x").with_y_axis("x2"));
+        assert!(matches!(graph.validate(), Err(Error::Invalid(_))));
+        let graph = Graph::default()
+            .with_xy_axis("x", "y")
+            .with_item(Function::expression("x").with_x_axis("y"));
+        assert!(matches!(graph.validate(), Err(Error::Invalid(_))));
+    }
+
+    #[test]
+    fn unbound_item_needs_default_axes() {
+        let graph = Graph::default()
+            .with_axis(Axis::named("time", "Time"))
+            .with_axis(Axis::named("value", "Value").with_direction(AxisDirection::Vertical))
+            .with_item(Xy::data("t", "v"));
+        assert!(matches!(graph.validate(), Err(Error::Invalid(_))));
+        let graph = Graph::default()
+            .with_xy_axis("Time", "Value")
+            .with_item(Xy::data("t", "v"));
+        assert!(graph.validate().is_ok());
+    }
+}
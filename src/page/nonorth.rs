@@ -0,0 +1,315 @@
+use crate::api1::{cmd, AsVueszApi1ValueStr, AutoName};
+use crate::style::marker::{Marker, MarkerFill, MarkerLine};
+use crate::style::plot::PlotLine;
+use crate::CommandLineEmbeddingInterface;
+use std::io::Write;
+
+/// The plotters within a [`Polar`](crate::page::polar::Polar) or
+/// [`Ternary`](crate::page::ternary::Ternary) graph.
+#[derive(derive_more::From)]
+pub enum NonOrthItem {
+    Point(NonOrthPoint),
+    Function(NonOrthFunction),
+}
+
+impl CommandLineEmbeddingInterface for NonOrthItem {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        match self {
+            NonOrthItem::Point(point) => point.write(writer),
+            NonOrthItem::Function(function) => function.write(writer),
+        }
+    }
+}
+
+/// Points and lines in non-orthogonal coordinates, like radius and angle of a polar
+/// graph.
+pub struct NonOrthPoint {
+    name: AutoName<Self>,
+    data1: String,
+    data2: String,
+    labels: Option<String>,
+    marker: Option<Marker>,
+    marker_size: Option<f32>,
+    marker_line: Option<MarkerLine>,
+    marker_fill: Option<MarkerFill>,
+    plot_line: Option<PlotLine>,
+    key_text: Option<String>,
+}
+
+impl NonOrthPoint {
+    pub fn data(data1: impl Into<String>, data2: impl Into<String>) -> Self {
+        Self {
+            name: AutoName::default(),
+            data1: data1.into(),
+            data2: data2.into(),
+            labels: None,
+            marker: None,
+            marker_size: None,
+            marker_line: None,
+            marker_fill: None,
+            plot_line: None,
+            key_text: None,
+        }
+    }
+
+    /// Text dataset with a label for each point.
+    pub fn set_labels(&mut self, dataset: impl Into<String>) {
+        self.labels = Some(dataset.into());
+    }
+
+    pub fn with_labels(mut self, dataset: impl Into<String>) -> Self {
+        self.set_labels(dataset);
+        self
+    }
+
+    pub fn set_marker(&mut self, marker: Marker) {
+        self.marker = Some(marker);
+    }
+
+    pub fn with_marker(mut self, marker: Marker) -> Self {
+        self.set_marker(marker);
+        self
+    }
+
+    pub fn set_marker_size(&mut self, pt: f32) {
+        self.marker_size = Some(pt);
+    }
+
+    pub fn with_marker_size(mut self, pt: f32) -> Self {
+        self.set_marker_size(pt);
+        self
+    }
+
+    pub fn set_marker_line(&mut self, marker_line: MarkerLine) {
+        self.marker_line = Some(marker_line);
+    }
+
+    pub fn with_marker_line(mut self, marker_line: MarkerLine) -> Self {
+        self.set_marker_line(marker_line);
+        self
+    }
+
+    pub fn set_marker_fill(&mut self, marker_fill: MarkerFill) {
+        self.marker_fill = Some(marker_fill);
+    }
+
+    pub fn with_marker_fill(mut self, marker_fill: MarkerFill) -> Self {
+        self.set_marker_fill(marker_fill);
+        self
+    }
+
+    pub fn set_plot_line(&mut self, plot_line: PlotLine) {
+        self.plot_line = Some(plot_line);
+    }
+
+    pub fn with_plot_line(mut self, plot_line: PlotLine) -> Self {
+        self.set_plot_line(plot_line);
+        self
+    }
+
+    /// Text shown for these points in the key of the graph.
+    pub fn set_key_text(&mut self, text: impl Into<String>) {
+        self.key_text = Some(text.into());
+    }
+
+    pub fn with_key_text(mut self, text: impl Into<String>) -> Self {
+        self.set_key_text(text);
+        self
+    }
+}
+
+impl CommandLineEmbeddingInterface for NonOrthPoint {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        cmd::Add("nonorthpoint", &self.name).write(writer)?;
+        cmd::ToUnique(&self.name).for_call(writer, |writer| {
+            cmd::Set("data1", &self.data1).write(writer)?;
+            cmd::Set("data2", &self.data2).write(writer)?;
+
+            if let Some(labels) = &self.labels {
+                cmd::Set("labels", labels).write(writer)?;
+            }
+
+            if let Some(marker) = &self.marker {
+                cmd::Set("marker", marker.as_veusz_api1_value_str()).write(writer)?;
+            }
+
+            if let Some(size) = self.marker_size {
+                cmd::Set("markerSize", &format!("{size}pt")).write(writer)?;
+            }
+
+            if let Some(marker_line) = &self.marker_line {
+                marker_line.write(writer)?;
+            }
+
+            if let Some(marker_fill) = &self.marker_fill {
+                marker_fill.write(writer)?;
+            }
+
+            if let Some(plot_line) = &self.plot_line {
+                plot_line.write(writer)?;
+            }
+
+            if let Some(text) = &self.key_text {
+                cmd::Set("key", text).write(writer)?;
+            }
+
+            Ok(())
+        })
+    }
+}
+
+/// The variable of the expression of a [`NonOrthFunction`].
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum NonOrthVariable {
+    /// The second coordinate is a function of the first, like the angle of the radius
+    A,
+    /// The first coordinate is a function of the second, like the radius of the angle
+    B,
+}
+
+impl NonOrthVariable {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            NonOrthVariable::A => "a",
+            NonOrthVariable::B => "b",
+        }
+    }
+}
+
+/// Plots an analytic expression in non-orthogonal coordinates, like `cos(b)` for the
+/// radius of a polar graph over the angle `b`.
+pub struct NonOrthFunction {
+    name: AutoName<Self>,
+    expression: String,
+    variable: Option<NonOrthVariable>,
+    steps: Option<u32>,
+    plot_line: Option<PlotLine>,
+    key_text: Option<String>,
+}
+
+impl NonOrthFunction {
+    pub fn expression(expression: impl Into<String>) -> Self {
+        Self {
+            name: AutoName::default(),
+            expression: expression.into(),
+            variable: None,
+            steps: None,
+            plot_line: None,
+            key_text: None,
+        }
+    }
+
+    pub fn set_variable(&mut self, variable: NonOrthVariable) {
+        self.variable = Some(variable);
+    }
+
+    pub fn with_variable(mut self, variable: NonOrthVariable) -> Self {
+        self.set_variable(variable);
+        self
+    }
+
+    /// Number of points the expression is evaluated at.
+    pub fn set_steps(&mut self, steps: u32) {
+        self.steps = Some(steps);
+    }
+
+    pub fn with_steps(mut self, steps: u32) -> Self {
+        self.set_steps(steps);
+        self
+    }
+
+    pub fn set_plot_line(&mut self, plot_line: PlotLine) {
+        self.plot_line = Some(plot_line);
+    }
+
+    pub fn with_plot_line(mut self, plot_line: PlotLine) -> Self {
+        self.set_plot_line(plot_line);
+        self
+    }
+
+    /// Text shown for this function in the key of the graph.
+    pub fn set_key_text(&mut self, text: impl Into<String>) {
+        self.key_text = Some(text.into());
+    }
+
+    pub fn with_key_text(mut self, text: impl Into<String>) -> Self {
+        self.set_key_text(text);
+        self
+    }
+}
+
+impl CommandLineEmbeddingInterface for NonOrthFunction {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        cmd::Add("nonorthfunc", &self.name).write(writer)?;
+        cmd::ToUnique(&self.name).for_call(writer, |writer| {
+            cmd::Set("function", &self.expression).write(writer)?;
+
+            if let Some(variable) = &self.variable {
+                cmd::Set("variable", variable.as_str()).write(writer)?;
+            }
+
+            if let Some(steps) = self.steps {
+                cmd::SetRaw("steps", steps).write(writer)?;
+            }
+
+            if let Some(plot_line) = &self.plot_line {
+                plot_line.write(writer)?;
+            }
+
+            if let Some(text) = &self.key_text {
+                cmd::Set("key", text).write(writer)?;
+            }
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api1::{written, PyStr};
+    use crate::page::ternary::{Ternary, TernaryMode};
+
+    #[test]
+    fn ternary_with_points_and_function() {
+        let point = NonOrthPoint::data("sand", "clay")
+            .with_marker(Marker::Diamond)
+            .with_key_text("soil");
+        let function = NonOrthFunction::expression("0.5 - a")
+            .with_variable(NonOrthVariable::A)
+            .with_steps(20);
+        let point_name = PyStr(&point.name).to_string();
+        let function_name = PyStr(&function.name).to_string();
+        let ternary = Ternary::default()
+            .with_mode(TernaryMode::Fraction)
+            .with_label_bottom("sand")
+            .with_reverse(true)
+            .with_item(point)
+            .with_item(function);
+        assert_eq!(
+            format!(
+                "Add(u'ternary', name=NAME, autoadd=False)\n\
+                 To(NAME)\n\
+                 Set(u'mode', u'fraction')\n\
+                 Set(u'labelbottom', u'sand')\n\
+                 Set(u'reverse', True)\n\
+                 Add(u'nonorthpoint', name={point_name}, autoadd=False)\n\
+                 To({point_name})\n\
+                 Set(u'data1', u'sand')\n\
+                 Set(u'data2', u'clay')\n\
+                 Set(u'marker', u'diamond')\n\
+                 Set(u'key', u'soil')\n\
+                 To('..')\n\
+                 Add(u'nonorthfunc', name={function_name}, autoadd=False)\n\
+                 To({function_name})\n\
+                 Set(u'function', u'0.5 - a')\n\
+                 Set(u'variable', u'a')\n\
+                 Set(u'steps', 20)\n\
+                 To('..')\n\
+                 To('..')\n"
+            ),
+            written(&ternary, ternary.name())
+        );
+    }
+}
@@ -0,0 +1,211 @@
+use crate::api1::{cmd, AsVueszApi1ValueStr, AutoName, PyNumber};
+use crate::page::nonorth::NonOrthItem;
+use crate::CommandLineEmbeddingInterface;
+use std::io::Write;
+
+/// Unit of the angles plotted in a [`Polar`] graph.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum AngleUnit {
+    Degrees,
+    Radians,
+}
+
+impl AsVueszApi1ValueStr for AngleUnit {
+    fn as_veusz_api1_value_str(&self) -> &str {
+        match self {
+            AngleUnit::Degrees => "degrees",
+            AngleUnit::Radians => "radians",
+        }
+    }
+}
+
+/// Direction of increasing angles in a [`Polar`] graph.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum AngleDirection {
+    Clockwise,
+    Anticlockwise,
+}
+
+impl AsVueszApi1ValueStr for AngleDirection {
+    fn as_veusz_api1_value_str(&self) -> &str {
+        match self {
+            AngleDirection::Clockwise => "clockwise",
+            AngleDirection::Anticlockwise => "anticlockwise",
+        }
+    }
+}
+
+/// Where the angle zero of a [`Polar`] graph points to.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum StartPosition {
+    Right,
+    Top,
+    Left,
+    Bottom,
+}
+
+impl AsVueszApi1ValueStr for StartPosition {
+    fn as_veusz_api1_value_str(&self) -> &str {
+        match self {
+            StartPosition::Right => "right",
+            StartPosition::Top => "top",
+            StartPosition::Left => "left",
+            StartPosition::Bottom => "bottom",
+        }
+    }
+}
+
+/// A graph with polar coordinates. Its children plot the radius as first and the angle
+/// as second coordinate.
+#[derive(Default)]
+pub struct Polar {
+    name: AutoName<Self>,
+    units: Option<AngleUnit>,
+    direction: Option<AngleDirection>,
+    start: Option<StartPosition>,
+    min_radius: Option<f64>,
+    max_radius: Option<f64>,
+    log: Option<bool>,
+    items: Vec<NonOrthItem>,
+}
+
+impl Polar {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn set_units(&mut self, units: AngleUnit) {
+        self.units = Some(units);
+    }
+
+    pub fn with_units(mut self, units: AngleUnit) -> Self {
+        self.set_units(units);
+        self
+    }
+
+    pub fn set_direction(&mut self, direction: AngleDirection) {
+        self.direction = Some(direction);
+    }
+
+    pub fn with_direction(mut self, direction: AngleDirection) -> Self {
+        self.set_direction(direction);
+        self
+    }
+
+    pub fn set_start(&mut self, start: StartPosition) {
+        self.start = Some(start);
+    }
+
+    pub fn with_start(mut self, start: StartPosition) -> Self {
+        self.set_start(start);
+        self
+    }
+
+    /// Radius at the centre, defaults to the minimum of the data.
+    pub fn set_min_radius(&mut self, min: f64) {
+        self.min_radius = Some(min);
+    }
+
+    pub fn with_min_radius(mut self, min: f64) -> Self {
+        self.set_min_radius(min);
+        self
+    }
+
+    /// Radius at the edge, defaults to the maximum of the data.
+    pub fn set_max_radius(&mut self, max: f64) {
+        self.max_radius = Some(max);
+    }
+
+    pub fn with_max_radius(mut self, max: f64) -> Self {
+        self.set_max_radius(max);
+        self
+    }
+
+    /// Logarithmic scale of the radius.
+    pub fn set_log(&mut self, log: bool) {
+        self.log = Some(log);
+    }
+
+    pub fn with_log(mut self, log: bool) -> Self {
+        self.set_log(log);
+        self
+    }
+
+    pub fn add(&mut self, item: impl Into<NonOrthItem>) {
+        self.items.push(item.into());
+    }
+
+    pub fn with_item(mut self, item: impl Into<NonOrthItem>) -> Self {
+        self.add(item);
+        self
+    }
+
+    pub fn with_items(mut self, items: impl IntoIterator<Item = impl Into<NonOrthItem>>) -> Self {
+        self.items.extend(items.into_iter().map(Into::into));
+        self
+    }
+}
+
+impl CommandLineEmbeddingInterface for Polar {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        cmd::Add("polar", &self.name).write(writer)?;
+        cmd::ToUnique(&self.name).for_call(writer, |writer| {
+            if let Some(units) = &self.units {
+                cmd::Set("units", units.as_veusz_api1_value_str()).write(writer)?;
+            }
+
+            if let Some(direction) = &self.direction {
+                cmd::Set("direction", direction.as_veusz_api1_value_str()).write(writer)?;
+            }
+
+            if let Some(start) = &self.start {
+                cmd::Set("position0", start.as_veusz_api1_value_str()).write(writer)?;
+            }
+
+            if let Some(min) = self.min_radius {
+                cmd::SetRaw("minradius", PyNumber(min)).write(writer)?;
+            }
+
+            if let Some(max) = self.max_radius {
+                cmd::SetRaw("maxradius", PyNumber(max)).write(writer)?;
+            }
+
+            if let Some(log) = &self.log {
+                cmd::SetRaw("log", log.as_veusz_api1_value_str()).write(writer)?;
+            }
+
+            for item in &self.items {
+                item.write(writer)?;
+            }
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api1::written;
+
+    #[test]
+    fn polar_in_degrees() {
+        let polar = Polar::default()
+            .with_units(AngleUnit::Degrees)
+            .with_direction(AngleDirection::Clockwise)
+            .with_start(StartPosition::Top)
+            .with_max_radius(10.0)
+            .with_log(true);
+        assert_eq!(
+            "Add(u'polar', name=NAME, autoadd=False)\n\
+             To(NAME)\n\
+             Set(u'units', u'degrees')\n\
+             Set(u'direction', u'clockwise')\n\
+             Set(u'position0', u'top')\n\
+             Set(u'maxradius', 10)\n\
+             Set(u'log', True)\n\
+             To('..')\n",
+            written(&polar, polar.name())
+        );
+    }
+}
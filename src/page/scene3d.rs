@@ -0,0 +1,975 @@
+use crate::api1::{cmd, AsVueszApi1ValueStr, AutoName, PyNumber};
+use crate::style::line::Line;
+use crate::style::marker::Marker;
+use crate::style::{ColorMap, ColorName};
+use crate::CommandLineEmbeddingInterface;
+use std::io::Write;
+
+/// How a [`Scene3D`] sorts the surfaces it draws.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum RenderMethod {
+    /// Fast, but may draw overlapping surfaces in the wrong order
+    Painters,
+    /// Binary space partitioning, slower but exact
+    Bsp,
+}
+
+impl AsVueszApi1ValueStr for RenderMethod {
+    fn as_veusz_api1_value_str(&self) -> &str {
+        match self {
+            RenderMethod::Painters => "painters",
+            RenderMethod::Bsp => "bsp",
+        }
+    }
+}
+
+/// The three lights of a [`Scene3D`], the settings groups `Lighting1` to `Lighting3`.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum LightSlot {
+    First,
+    Second,
+    Third,
+}
+
+impl LightSlot {
+    const fn index(&self) -> usize {
+        match self {
+            LightSlot::First => 0,
+            LightSlot::Second => 1,
+            LightSlot::Third => 2,
+        }
+    }
+}
+
+/// One of the three lights of a [`Scene3D`].
+#[derive(Default)]
+pub struct Light {
+    enable: Option<bool>,
+    color: Option<String>,
+    intensity: Option<f64>,
+    position: Option<(f64, f64, f64)>,
+}
+
+impl Light {
+    pub fn set_enable(&mut self, enable: bool) {
+        self.enable = Some(enable);
+    }
+
+    pub fn with_enable(mut self, enable: bool) -> Self {
+        self.set_enable(enable);
+        self
+    }
+
+    pub fn set_color(&mut self, color: impl Into<String>) {
+        self.color = Some(color.into());
+    }
+
+    pub fn with_color(mut self, color: impl Into<String>) -> Self {
+        self.set_color(color);
+        self
+    }
+
+    pub fn set_color_by_name(&mut self, color_name: ColorName) {
+        self.color = Some(color_name.as_veusz_api1_value_str().to_string());
+    }
+
+    pub fn with_color_by_name(mut self, color_name: ColorName) -> Self {
+        self.set_color_by_name(color_name);
+        self
+    }
+
+    /// Intensity in percent.
+    pub fn set_intensity(&mut self, percent: f64) {
+        self.intensity = Some(percent);
+    }
+
+    pub fn with_intensity(mut self, percent: f64) -> Self {
+        self.set_intensity(percent);
+        self
+    }
+
+    /// Position relative to the camera.
+    pub fn set_position(&mut self, x: f64, y: f64, z: f64) {
+        self.position = Some((x, y, z));
+    }
+
+    pub fn with_position(mut self, x: f64, y: f64, z: f64) -> Self {
+        self.set_position(x, y, z);
+        self
+    }
+
+    fn write_as<W: Write>(&self, group: &str, writer: &mut W) -> std::io::Result<()> {
+        if let Some(enable) = &self.enable {
+            cmd::SetRaw(&format!("{group}/enable"), enable.as_veusz_api1_value_str())
+                .write(writer)?;
+        }
+
+        if let Some(color) = &self.color {
+            cmd::Set(&format!("{group}/color"), color).write(writer)?;
+        }
+
+        if let Some(intensity) = self.intensity {
+            cmd::SetRaw(&format!("{group}/intensity"), PyNumber(intensity)).write(writer)?;
+        }
+
+        if let Some((x, y, z)) = self.position {
+            cmd::SetRaw(&format!("{group}/x"), PyNumber(x)).write(writer)?;
+            cmd::SetRaw(&format!("{group}/y"), PyNumber(y)).write(writer)?;
+            cmd::SetRaw(&format!("{group}/z"), PyNumber(z)).write(writer)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A three dimensional scene on a page, viewed by a camera and lit by up to three
+/// lights.
+#[derive(Default)]
+pub struct Scene3D {
+    name: AutoName<Self>,
+    render_method: Option<RenderMethod>,
+    rotation: Option<(f64, f64, f64)>,
+    distance: Option<f64>,
+    lights: [Option<Light>; 3],
+    graphs: Vec<Graph3D>,
+}
+
+impl Scene3D {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn set_render_method(&mut self, method: RenderMethod) {
+        self.render_method = Some(method);
+    }
+
+    pub fn with_render_method(mut self, method: RenderMethod) -> Self {
+        self.set_render_method(method);
+        self
+    }
+
+    /// Rotation of the camera around the x, y and z axes in degrees.
+    pub fn set_rotation(&mut self, x: f64, y: f64, z: f64) {
+        self.rotation = Some((x, y, z));
+    }
+
+    pub fn with_rotation(mut self, x: f64, y: f64, z: f64) -> Self {
+        self.set_rotation(x, y, z);
+        self
+    }
+
+    /// Distance of the camera from the centre of the scene.
+    pub fn set_distance(&mut self, distance: f64) {
+        self.distance = Some(distance);
+    }
+
+    pub fn with_distance(mut self, distance: f64) -> Self {
+        self.set_distance(distance);
+        self
+    }
+
+    /// Configures one of the three lights, replacing an earlier one in the same slot.
+    pub fn set_light(&mut self, slot: LightSlot, light: Light) {
+        self.lights[slot.index()] = Some(light);
+    }
+
+    pub fn with_light(mut self, slot: LightSlot, light: Light) -> Self {
+        self.set_light(slot, light);
+        self
+    }
+
+    pub fn add_graph(&mut self, graph: Graph3D) {
+        self.graphs.push(graph);
+    }
+
+    pub fn with_graph(mut self, graph: Graph3D) -> Self {
+        self.add_graph(graph);
+        self
+    }
+}
+
+impl CommandLineEmbeddingInterface for Scene3D {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        cmd::Add("scene3d", &self.name).write(writer)?;
+        cmd::ToUnique(&self.name).for_call(writer, |writer| {
+            if let Some(method) = &self.render_method {
+                cmd::Set("renderMethod", method.as_veusz_api1_value_str()).write(writer)?;
+            }
+
+            if let Some((x, y, z)) = self.rotation {
+                cmd::SetRaw("xRotation", PyNumber(x)).write(writer)?;
+                cmd::SetRaw("yRotation", PyNumber(y)).write(writer)?;
+                cmd::SetRaw("zRotation", PyNumber(z)).write(writer)?;
+            }
+
+            if let Some(distance) = self.distance {
+                cmd::SetRaw("distance", PyNumber(distance)).write(writer)?;
+            }
+
+            for (index, light) in self.lights.iter().enumerate() {
+                if let Some(light) = light {
+                    light.write_as(&format!("Lighting{}", index + 1), writer)?;
+                }
+            }
+
+            for graph in &self.graphs {
+                graph.write(writer)?;
+            }
+
+            Ok(())
+        })
+    }
+}
+
+/// A box with three axes within a [`Scene3D`].
+///
+/// Like all widgets it is added with `autoadd=False`, so [`Graph3D::default`] has no
+/// axes. Add them with [`Graph3D::with_xyz_axis`] or [`Graph3D::with_axis`].
+#[derive(Default)]
+pub struct Graph3D {
+    name: AutoName<Self>,
+    axes: Vec<Axis3D>,
+    items: Vec<Graph3DItem>,
+}
+
+impl Graph3D {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn add_axis(&mut self, axis: Axis3D) {
+        self.axes.push(axis);
+    }
+
+    pub fn with_axis(mut self, axis: Axis3D) -> Self {
+        self.add_axis(axis);
+        self
+    }
+
+    pub fn with_xyz_axis(
+        mut self,
+        x: impl Into<String>,
+        y: impl Into<String>,
+        z: impl Into<String>,
+    ) -> Self {
+        self.add_axis(Axis3D::x(x));
+        self.add_axis(Axis3D::y(y));
+        self.add_axis(Axis3D::z(z));
+        self
+    }
+
+    pub fn add(&mut self, item: impl Into<Graph3DItem>) {
+        self.items.push(item.into());
+    }
+
+    pub fn with_item(mut self, item: impl Into<Graph3DItem>) -> Self {
+        self.add(item);
+        self
+    }
+
+    pub fn with_items(mut self, items: impl IntoIterator<Item = impl Into<Graph3DItem>>) -> Self {
+        self.items.extend(items.into_iter().map(Into::into));
+        self
+    }
+}
+
+impl CommandLineEmbeddingInterface for Graph3D {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        cmd::Add("graph3d", &self.name).write(writer)?;
+        cmd::ToUnique(&self.name).for_call(writer, |writer| {
+            for axis in &self.axes {
+                axis.write(writer)?;
+            }
+            for item in &self.items {
+                item.write(writer)?;
+            }
+            Ok(())
+        })
+    }
+}
+
+/// The plotters within a [`Graph3D`].
+#[derive(derive_more::From)]
+pub enum Graph3DItem {
+    Point(Point3D),
+    Function(Function3D),
+    Surface(Surface3D),
+    Volume(Volume3D),
+}
+
+impl CommandLineEmbeddingInterface for Graph3DItem {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        match self {
+            Graph3DItem::Point(point) => point.write(writer),
+            Graph3DItem::Function(function) => function.write(writer),
+            Graph3DItem::Surface(surface) => surface.write(writer),
+            Graph3DItem::Volume(volume) => volume.write(writer),
+        }
+    }
+}
+
+/// The direction of an [`Axis3D`].
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum Direction3D {
+    X,
+    Y,
+    Z,
+}
+
+impl Direction3D {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Direction3D::X => "x",
+            Direction3D::Y => "y",
+            Direction3D::Z => "z",
+        }
+    }
+}
+
+pub struct Axis3D {
+    name: String,
+    label: String,
+    direction: Direction3D,
+    min: Option<f64>,
+    max: Option<f64>,
+    log: Option<bool>,
+}
+
+impl Axis3D {
+    pub fn x(label: impl Into<String>) -> Self {
+        Self::new(label.into(), Direction3D::X)
+    }
+
+    pub fn y(label: impl Into<String>) -> Self {
+        Self::new(label.into(), Direction3D::Y)
+    }
+
+    pub fn z(label: impl Into<String>) -> Self {
+        Self::new(label.into(), Direction3D::Z)
+    }
+
+    fn new(label: String, direction: Direction3D) -> Self {
+        Self {
+            name: direction.as_str().into(),
+            label,
+            direction,
+            min: None,
+            max: None,
+            log: None,
+        }
+    }
+
+    pub fn with_min(mut self, min: impl Into<Option<f64>>) -> Self {
+        self.min = min.into();
+        self
+    }
+
+    pub fn with_max(mut self, max: impl Into<Option<f64>>) -> Self {
+        self.max = max.into();
+        self
+    }
+
+    pub fn with_log(mut self, log: bool) -> Self {
+        self.log = Some(log);
+        self
+    }
+}
+
+impl CommandLineEmbeddingInterface for Axis3D {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        cmd::Add("axis3d", &self.name).write(writer)?;
+        cmd::ToUnique(&self.name).for_call(writer, |writer| {
+            cmd::Set("label", &self.label).write(writer)?;
+            cmd::Set("direction", self.direction.as_str()).write(writer)?;
+
+            if let Some(min) = self.min {
+                cmd::SetRaw("min", PyNumber(min)).write(writer)?;
+            }
+
+            if let Some(max) = self.max {
+                cmd::SetRaw("max", PyNumber(max)).write(writer)?;
+            }
+
+            if let Some(log) = &self.log {
+                cmd::SetRaw("log", log.as_veusz_api1_value_str()).write(writer)?;
+            }
+
+            Ok(())
+        })
+    }
+}
+
+/// Points and lines through three datasets of the x, y and z coordinates.
+pub struct Point3D {
+    name: AutoName<Self>,
+    x_data: String,
+    y_data: String,
+    z_data: String,
+    marker: Option<Marker>,
+    marker_size: Option<f32>,
+    line: Option<Line>,
+}
+
+impl Point3D {
+    pub fn data(
+        x_data: impl Into<String>,
+        y_data: impl Into<String>,
+        z_data: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: AutoName::default(),
+            x_data: x_data.into(),
+            y_data: y_data.into(),
+            z_data: z_data.into(),
+            marker: None,
+            marker_size: None,
+            line: None,
+        }
+    }
+
+    pub fn set_marker(&mut self, marker: Marker) {
+        self.marker = Some(marker);
+    }
+
+    pub fn with_marker(mut self, marker: Marker) -> Self {
+        self.set_marker(marker);
+        self
+    }
+
+    pub fn set_marker_size(&mut self, pt: f32) {
+        self.marker_size = Some(pt);
+    }
+
+    pub fn with_marker_size(mut self, pt: f32) -> Self {
+        self.set_marker_size(pt);
+        self
+    }
+
+    /// Line connecting the points.
+    pub fn set_line(&mut self, line: Line) {
+        self.line = Some(line);
+    }
+
+    pub fn with_line(mut self, line: Line) -> Self {
+        self.set_line(line);
+        self
+    }
+}
+
+impl CommandLineEmbeddingInterface for Point3D {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        cmd::Add("point3d", &self.name).write(writer)?;
+        cmd::ToUnique(&self.name).for_call(writer, |writer| {
+            cmd::Set("xData", &self.x_data).write(writer)?;
+            cmd::Set("yData", &self.y_data).write(writer)?;
+            cmd::Set("zData", &self.z_data).write(writer)?;
+
+            if let Some(marker) = &self.marker {
+                cmd::Set("marker", marker.as_veusz_api1_value_str()).write(writer)?;
+            }
+
+            if let Some(size) = self.marker_size {
+                cmd::Set("markerSize", &format!("{size}pt")).write(writer)?;
+            }
+
+            if let Some(line) = &self.line {
+                line.write_as("PlotLine", writer)?;
+            }
+
+            Ok(())
+        })
+    }
+}
+
+/// What the expressions of a [`Function3D`] describe.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum FunctionMode3D {
+    ZOfXy,
+    XOfYz,
+    YOfXz,
+    Parametric,
+}
+
+impl AsVueszApi1ValueStr for FunctionMode3D {
+    fn as_veusz_api1_value_str(&self) -> &str {
+        match self {
+            FunctionMode3D::ZOfXy => "z=fn(x,y)",
+            FunctionMode3D::XOfYz => "x=fn(y,z)",
+            FunctionMode3D::YOfXz => "y=fn(x,z)",
+            FunctionMode3D::Parametric => "x,y,z=fns(t)",
+        }
+    }
+}
+
+/// Plots an expression as surface over two coordinates or as line over a parameter `t`.
+pub struct Function3D {
+    name: AutoName<Self>,
+    mode: FunctionMode3D,
+    fn_x: Option<String>,
+    fn_y: Option<String>,
+    fn_z: Option<String>,
+    line_steps: Option<u32>,
+    surface_steps: Option<u32>,
+    line: Option<Line>,
+}
+
+impl Function3D {
+    /// A surface `z = f(x, y)`.
+    pub fn z_of_xy(expression: impl Into<String>) -> Self {
+        Self::new(FunctionMode3D::ZOfXy, None, None, Some(expression.into()))
+    }
+
+    /// A surface `x = f(y, z)`.
+    pub fn x_of_yz(expression: impl Into<String>) -> Self {
+        Self::new(FunctionMode3D::XOfYz, Some(expression.into()), None, None)
+    }
+
+    /// A surface `y = f(x, z)`.
+    pub fn y_of_xz(expression: impl Into<String>) -> Self {
+        Self::new(FunctionMode3D::YOfXz, None, Some(expression.into()), None)
+    }
+
+    /// A line with the coordinates given by three expressions of `t` from 0 to 1.
+    pub fn parametric(x: impl Into<String>, y: impl Into<String>, z: impl Into<String>) -> Self {
+        Self::new(
+            FunctionMode3D::Parametric,
+            Some(x.into()),
+            Some(y.into()),
+            Some(z.into()),
+        )
+    }
+
+    fn new(
+        mode: FunctionMode3D,
+        fn_x: Option<String>,
+        fn_y: Option<String>,
+        fn_z: Option<String>,
+    ) -> Self {
+        Self {
+            name: AutoName::default(),
+            mode,
+            fn_x,
+            fn_y,
+            fn_z,
+            line_steps: None,
+            surface_steps: None,
+            line: None,
+        }
+    }
+
+    /// Number of points of a parametric line.
+    pub fn set_line_steps(&mut self, steps: u32) {
+        self.line_steps = Some(steps);
+    }
+
+    pub fn with_line_steps(mut self, steps: u32) -> Self {
+        self.set_line_steps(steps);
+        self
+    }
+
+    /// Number of points along each side of a surface.
+    pub fn set_surface_steps(&mut self, steps: u32) {
+        self.surface_steps = Some(steps);
+    }
+
+    pub fn with_surface_steps(mut self, steps: u32) -> Self {
+        self.set_surface_steps(steps);
+        self
+    }
+
+    pub fn set_line(&mut self, line: Line) {
+        self.line = Some(line);
+    }
+
+    pub fn with_line(mut self, line: Line) -> Self {
+        self.set_line(line);
+        self
+    }
+}
+
+impl CommandLineEmbeddingInterface for Function3D {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        cmd::Add("function3d", &self.name).write(writer)?;
+        cmd::ToUnique(&self.name).for_call(writer, |writer| {
+            cmd::Set("mode", self.mode.as_veusz_api1_value_str()).write(writer)?;
+
+            if let Some(expression) = &self.fn_x {
+                cmd::Set("fnx", expression).write(writer)?;
+            }
+
+            if let Some(expression) = &self.fn_y {
+                cmd::Set("fny", expression).write(writer)?;
+            }
+
+            if let Some(expression) = &self.fn_z {
+                cmd::Set("fnz", expression).write(writer)?;
+            }
+
+            if let Some(steps) = self.line_steps {
+                cmd::SetRaw("linesteps", steps).write(writer)?;
+            }
+
+            if let Some(steps) = self.surface_steps {
+                cmd::SetRaw("surfacesteps", steps).write(writer)?;
+            }
+
+            if let Some(line) = &self.line {
+                line.write_as("Line", writer)?;
+            }
+
+            Ok(())
+        })
+    }
+}
+
+/// Which coordinate the values of the dataset of a [`Surface3D`] are.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum SurfaceMode {
+    ZOfXy,
+    XOfYz,
+    YOfXz,
+}
+
+impl AsVueszApi1ValueStr for SurfaceMode {
+    fn as_veusz_api1_value_str(&self) -> &str {
+        match self {
+            SurfaceMode::ZOfXy => "z(x,y)",
+            SurfaceMode::XOfYz => "x(y,z)",
+            SurfaceMode::YOfXz => "y(x,z)",
+        }
+    }
+}
+
+/// A surface of the values of a two dimensional dataset.
+pub struct Surface3D {
+    name: AutoName<Self>,
+    data: String,
+    mode: Option<SurfaceMode>,
+    color_map: Option<ColorMap>,
+    high_resolution: Option<bool>,
+    line: Option<Line>,
+}
+
+impl Surface3D {
+    pub fn data(data: impl Into<String>) -> Self {
+        Self {
+            name: AutoName::default(),
+            data: data.into(),
+            mode: None,
+            color_map: None,
+            high_resolution: None,
+            line: None,
+        }
+    }
+
+    pub fn set_mode(&mut self, mode: SurfaceMode) {
+        self.mode = Some(mode);
+    }
+
+    pub fn with_mode(mut self, mode: SurfaceMode) -> Self {
+        self.set_mode(mode);
+        self
+    }
+
+    pub fn set_color_map(&mut self, color_map: ColorMap) {
+        self.color_map = Some(color_map);
+    }
+
+    pub fn with_color_map(mut self, color_map: ColorMap) -> Self {
+        self.set_color_map(color_map);
+        self
+    }
+
+    /// Interpolates between the values instead of drawing one tile per value.
+    pub fn set_high_resolution(&mut self, high_resolution: bool) {
+        self.high_resolution = Some(high_resolution);
+    }
+
+    pub fn with_high_resolution(mut self, high_resolution: bool) -> Self {
+        self.set_high_resolution(high_resolution);
+        self
+    }
+
+    /// Grid lines on the surface.
+    pub fn set_line(&mut self, line: Line) {
+        self.line = Some(line);
+    }
+
+    pub fn with_line(mut self, line: Line) -> Self {
+        self.set_line(line);
+        self
+    }
+}
+
+impl CommandLineEmbeddingInterface for Surface3D {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        cmd::Add("surface3d", &self.name).write(writer)?;
+        cmd::ToUnique(&self.name).for_call(writer, |writer| {
+            cmd::Set("data", &self.data).write(writer)?;
+
+            if let Some(mode) = &self.mode {
+                cmd::Set("mode", mode.as_veusz_api1_value_str()).write(writer)?;
+            }
+
+            if let Some(color_map) = &self.color_map {
+                cmd::Set("colorMap", color_map.as_veusz_api1_value_str()).write(writer)?;
+            }
+
+            if let Some(high_resolution) = &self.high_resolution {
+                cmd::SetRaw("highres", high_resolution.as_veusz_api1_value_str()).write(writer)?;
+            }
+
+            if let Some(line) = &self.line {
+                line.write_as("Line", writer)?;
+            }
+
+            Ok(())
+        })
+    }
+}
+
+/// Cubes at the given x, y and z coordinates, coloured by a dataset of values.
+pub struct Volume3D {
+    name: AutoName<Self>,
+    x_data: String,
+    y_data: String,
+    z_data: String,
+    values: String,
+    transparency_data: Option<String>,
+    color_map: Option<ColorMap>,
+    min: Option<f64>,
+    max: Option<f64>,
+}
+
+impl Volume3D {
+    pub fn data(
+        x_data: impl Into<String>,
+        y_data: impl Into<String>,
+        z_data: impl Into<String>,
+        values: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: AutoName::default(),
+            x_data: x_data.into(),
+            y_data: y_data.into(),
+            z_data: z_data.into(),
+            values: values.into(),
+            transparency_data: None,
+            color_map: None,
+            min: None,
+            max: None,
+        }
+    }
+
+    /// Dataset with the transparency of each cube, from 0 to 1.
+    pub fn set_transparency_data(&mut self, dataset: impl Into<String>) {
+        self.transparency_data = Some(dataset.into());
+    }
+
+    pub fn with_transparency_data(mut self, dataset: impl Into<String>) -> Self {
+        self.set_transparency_data(dataset);
+        self
+    }
+
+    pub fn set_color_map(&mut self, color_map: ColorMap) {
+        self.color_map = Some(color_map);
+    }
+
+    pub fn with_color_map(mut self, color_map: ColorMap) -> Self {
+        self.set_color_map(color_map);
+        self
+    }
+
+    /// Value of the first colour of the map, defaults to the minimum of the values.
+    pub fn set_min(&mut self, min: f64) {
+        self.min = Some(min);
+    }
+
+    pub fn with_min(mut self, min: f64) -> Self {
+        self.set_min(min);
+        self
+    }
+
+    /// Value of the last colour of the map, defaults to the maximum of the values.
+    pub fn set_max(&mut self, max: f64) {
+        self.max = Some(max);
+    }
+
+    pub fn with_max(mut self, max: f64) -> Self {
+        self.set_max(max);
+        self
+    }
+}
+
+impl CommandLineEmbeddingInterface for Volume3D {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        cmd::Add("volume3d", &self.name).write(writer)?;
+        cmd::ToUnique(&self.name).for_call(writer, |writer| {
+            cmd::Set("xData", &self.x_data).write(writer)?;
+            cmd::Set("yData", &self.y_data).write(writer)?;
+            cmd::Set("zData", &self.z_data).write(writer)?;
+            cmd::Set("data", &self.values).write(writer)?;
+
+            if let Some(dataset) = &self.transparency_data {
+                cmd::Set("transData", dataset).write(writer)?;
+            }
+
+            if let Some(color_map) = &self.color_map {
+                cmd::Set("colorMap", color_map.as_veusz_api1_value_str()).write(writer)?;
+            }
+
+            if let Some(min) = self.min {
+                cmd::SetRaw("min", PyNumber(min)).write(writer)?;
+            }
+
+            if let Some(max) = self.max {
+                cmd::SetRaw("max", PyNumber(max)).write(writer)?;
+            }
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api1::{written, PyStr};
+
+    #[test]
+    fn scene_with_surface_function() {
+        let function = Function3D::z_of_xy("x * y").with_surface_steps(30);
+        let function_name = PyStr(&function.name).to_string();
+        let graph = Graph3D::default()
+            .with_axis(Axis3D::x("pressure").with_log(true))
+            .with_item(function);
+        let graph_name = PyStr(graph.name()).to_string();
+        let scene = Scene3D::default()
+            .with_render_method(RenderMethod::Bsp)
+            .with_rotation(30.0, 45.0, 0.0)
+            .with_light(LightSlot::Second, Light::default().with_intensity(80.0))
+            .with_graph(graph);
+        assert_eq!(
+            format!(
+                "Add(u'scene3d', name=NAME, autoadd=False)\n\
+                 To(NAME)\n\
+                 Set(u'renderMethod', u'bsp')\n\
+                 Set(u'xRotation', 30)\n\
+                 Set(u'yRotation', 45)\n\
+                 Set(u'zRotation', 0)\n\
+                 Set(u'Lighting2/intensity', 80)\n\
+                 Add(u'graph3d', name={graph_name}, autoadd=False)\n\
+                 To({graph_name})\n\
+                 Add(u'axis3d', name=u'x', autoadd=False)\n\
+                 To(u'x')\n\
+                 Set(u'label', u'pressure')\n\
+                 Set(u'direction', u'x')\n\
+                 Set(u'log', True)\n\
+                 To('..')\n\
+                 Add(u'function3d', name={function_name}, autoadd=False)\n\
+                 To({function_name})\n\
+                 Set(u'mode', u'z=fn(x,y)')\n\
+                 Set(u'fnz', u'x * y')\n\
+                 Set(u'surfacesteps', 30)\n\
+                 To('..')\n\
+                 To('..')\n\
+                 To('..')\n"
+            ),
+            written(&scene, scene.name())
+        );
+    }
+
+    #[test]
+    fn parametric_function_line() {
+        let function = Function3D::parametric("sin(t)", "cos(t)", "t").with_line_steps(100);
+        assert_eq!(
+            "Add(u'function3d', name=NAME, autoadd=False)\n\
+             To(NAME)\n\
+             Set(u'mode', u'x,y,z=fns(t)')\n\
+             Set(u'fnx', u'sin(t)')\n\
+             Set(u'fny', u'cos(t)')\n\
+             Set(u'fnz', u't')\n\
+             Set(u'linesteps', 100)\n\
+             To('..')\n",
+            written(&function, &function.name)
+        );
+    }
+
+    #[test]
+    fn lights_replace_their_slot() {
+        let scene = Scene3D::default()
+            .with_light(LightSlot::First, Light::default().with_intensity(10.0))
+            .with_light(LightSlot::Third, Light::default().with_enable(false))
+            .with_light(LightSlot::First, Light::default().with_intensity(20.0));
+        assert_eq!(
+            "Add(u'scene3d', name=NAME, autoadd=False)\n\
+             To(NAME)\n\
+             Set(u'Lighting1/intensity', 20)\n\
+             Set(u'Lighting3/enable', False)\n\
+             To('..')\n",
+            written(&scene, scene.name())
+        );
+    }
+
+    #[test]
+    fn points_with_line() {
+        let point = Point3D::data("x", "y", "z")
+            .with_marker(Marker::Circle)
+            .with_marker_size(2.5)
+            .with_line(Line::default().with_width(0.5));
+        assert_eq!(
+            "Add(u'point3d', name=NAME, autoadd=False)\n\
+             To(NAME)\n\
+             Set(u'xData', u'x')\n\
+             Set(u'yData', u'y')\n\
+             Set(u'zData', u'z')\n\
+             Set(u'marker', u'circle')\n\
+             Set(u'markerSize', u'2.5pt')\n\
+             Set(u'PlotLine/width', u'0.5pt')\n\
+             To('..')\n",
+            written(&point, &point.name)
+        );
+    }
+
+    #[test]
+    fn surface_modes() {
+        for (mode, value) in [
+            (SurfaceMode::ZOfXy, "z(x,y)"),
+            (SurfaceMode::XOfYz, "x(y,z)"),
+            (SurfaceMode::YOfXz, "y(x,z)"),
+        ] {
+            let surface = Surface3D::data("heights").with_mode(mode);
+            assert_eq!(
+                format!(
+                    "Add(u'surface3d', name=NAME, autoadd=False)\n\
+                     To(NAME)\n\
+                     Set(u'data', u'heights')\n\
+                     Set(u'mode', u'{value}')\n\
+                     To('..')\n"
+                ),
+                written(&surface, &surface.name)
+            );
+        }
+    }
+
+    #[test]
+    fn volume_of_cubes() {
+        let volume = Volume3D::data("x", "y", "z", "density")
+            .with_transparency_data("alpha")
+            .with_min(0.0)
+            .with_max(2.5);
+        assert_eq!(
+            "Add(u'volume3d', name=NAME, autoadd=False)\n\
+             To(NAME)\n\
+             Set(u'xData', u'x')\n\
+             Set(u'yData', u'y')\n\
+             Set(u'zData', u'z')\n\
+             Set(u'data', u'density')\n\
+             Set(u'transData', u'alpha')\n\
+             Set(u'min', 0)\n\
+             Set(u'max', 2.5)\n\
+             To('..')\n",
+            written(&volume, &volume.name)
+        );
+    }
+}
@@ -0,0 +1,570 @@
+use crate::api1::{cmd, AsVueszApi1ValueStr, AutoName, PyList, PyNumber};
+use crate::page::Positioning;
+use crate::style::arrow::Arrow;
+use crate::style::fill::Fill;
+use crate::style::line::Line;
+use crate::CommandLineEmbeddingInterface;
+use std::io::Write;
+
+/// Centre, size and rotation of a [`Rect`], [`Ellipse`] or [`ImageFile`].
+struct BoxPosition {
+    positioning: Option<Positioning>,
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+    rotation: Option<f64>,
+}
+
+impl BoxPosition {
+    fn new(x: f64, y: f64, width: f64, height: f64) -> Self {
+        Self {
+            positioning: None,
+            x,
+            y,
+            width,
+            height,
+            rotation: None,
+        }
+    }
+
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        if let Some(positioning) = &self.positioning {
+            cmd::Set("positioning", positioning.as_str()).write(writer)?;
+        }
+
+        cmd::SetRaw("xPos", PyList([PyNumber(self.x)])).write(writer)?;
+        cmd::SetRaw("yPos", PyList([PyNumber(self.y)])).write(writer)?;
+        cmd::SetRaw("width", PyList([PyNumber(self.width)])).write(writer)?;
+        cmd::SetRaw("height", PyList([PyNumber(self.height)])).write(writer)?;
+
+        if let Some(rotation) = self.rotation {
+            cmd::SetRaw("rotate", PyList([PyNumber(rotation)])).write(writer)?;
+        }
+
+        Ok(())
+    }
+}
+/// A rectangle, placed by its centre and size as fractions of the page or graph, or in
+/// axis coordinates.
+pub struct Rect {
+    name: AutoName<Self>,
+    position: BoxPosition,
+    rounding: Option<u32>,
+    border: Option<Line>,
+    fill: Option<Fill>,
+}
+
+impl Rect {
+    pub fn at(x: f64, y: f64, width: f64, height: f64) -> Self {
+        Self {
+            name: AutoName::default(),
+            position: BoxPosition::new(x, y, width, height),
+            rounding: None,
+            border: None,
+            fill: None,
+        }
+    }
+
+    pub fn set_positioning(&mut self, positioning: Positioning) {
+        self.position.positioning = Some(positioning);
+    }
+
+    pub fn with_positioning(mut self, positioning: Positioning) -> Self {
+        self.set_positioning(positioning);
+        self
+    }
+
+    /// Rotation in degrees around the centre.
+    pub fn set_rotation(&mut self, degrees: f64) {
+        self.position.rotation = Some(degrees);
+    }
+
+    pub fn with_rotation(mut self, degrees: f64) -> Self {
+        self.set_rotation(degrees);
+        self
+    }
+
+    /// Rounding of the corners, from 0 to 100.
+    pub fn set_rounding(&mut self, rounding: u32) {
+        self.rounding = Some(rounding);
+    }
+
+    pub fn with_rounding(mut self, rounding: u32) -> Self {
+        self.set_rounding(rounding);
+        self
+    }
+
+    pub fn set_border(&mut self, border: Line) {
+        self.border = Some(border);
+    }
+
+    pub fn with_border(mut self, border: Line) -> Self {
+        self.set_border(border);
+        self
+    }
+
+    pub fn set_fill(&mut self, fill: Fill) {
+        self.fill = Some(fill);
+    }
+
+    pub fn with_fill(mut self, fill: Fill) -> Self {
+        self.set_fill(fill);
+        self
+    }
+}
+
+impl CommandLineEmbeddingInterface for Rect {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        cmd::Add("rect", &self.name).write(writer)?;
+        cmd::ToUnique(&self.name).for_call(writer, |writer| {
+            self.position.write(writer)?;
+
+            if let Some(rounding) = self.rounding {
+                cmd::SetRaw("rounding", rounding).write(writer)?;
+            }
+
+            if let Some(border) = &self.border {
+                border.write_as("Border", writer)?;
+            }
+
+            if let Some(fill) = &self.fill {
+                fill.write_as("Fill", writer)?;
+            }
+
+            Ok(())
+        })
+    }
+}
+
+/// An ellipse, placed by its centre and size like a [`Rect`].
+pub struct Ellipse {
+    name: AutoName<Self>,
+    position: BoxPosition,
+    border: Option<Line>,
+    fill: Option<Fill>,
+}
+
+impl Ellipse {
+    pub fn at(x: f64, y: f64, width: f64, height: f64) -> Self {
+        Self {
+            name: AutoName::default(),
+            position: BoxPosition::new(x, y, width, height),
+            border: None,
+            fill: None,
+        }
+    }
+
+    pub fn set_positioning(&mut self, positioning: Positioning) {
+        self.position.positioning = Some(positioning);
+    }
+
+    pub fn with_positioning(mut self, positioning: Positioning) -> Self {
+        self.set_positioning(positioning);
+        self
+    }
+
+    /// Rotation in degrees around the centre.
+    pub fn set_rotation(&mut self, degrees: f64) {
+        self.position.rotation = Some(degrees);
+    }
+
+    pub fn with_rotation(mut self, degrees: f64) -> Self {
+        self.set_rotation(degrees);
+        self
+    }
+
+    pub fn set_border(&mut self, border: Line) {
+        self.border = Some(border);
+    }
+
+    pub fn with_border(mut self, border: Line) -> Self {
+        self.set_border(border);
+        self
+    }
+
+    pub fn set_fill(&mut self, fill: Fill) {
+        self.fill = Some(fill);
+    }
+
+    pub fn with_fill(mut self, fill: Fill) -> Self {
+        self.set_fill(fill);
+        self
+    }
+}
+
+impl CommandLineEmbeddingInterface for Ellipse {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        cmd::Add("ellipse", &self.name).write(writer)?;
+        cmd::ToUnique(&self.name).for_call(writer, |writer| {
+            self.position.write(writer)?;
+
+            if let Some(border) = &self.border {
+                border.write_as("Border", writer)?;
+            }
+
+            if let Some(fill) = &self.fill {
+                fill.write_as("Fill", writer)?;
+            }
+
+            Ok(())
+        })
+    }
+}
+
+/// An image file, like a logo, placed by its centre and size like a [`Rect`].
+pub struct ImageFile {
+    name: AutoName<Self>,
+    filename: String,
+    position: BoxPosition,
+    keep_aspect: Option<bool>,
+    border: Option<Line>,
+}
+
+impl ImageFile {
+    pub fn at(filename: impl Into<String>, x: f64, y: f64, width: f64, height: f64) -> Self {
+        Self {
+            name: AutoName::default(),
+            filename: filename.into(),
+            position: BoxPosition::new(x, y, width, height),
+            keep_aspect: None,
+            border: None,
+        }
+    }
+
+    pub fn set_positioning(&mut self, positioning: Positioning) {
+        self.position.positioning = Some(positioning);
+    }
+
+    pub fn with_positioning(mut self, positioning: Positioning) -> Self {
+        self.set_positioning(positioning);
+        self
+    }
+
+    /// Rotation in degrees around the centre.
+    pub fn set_rotation(&mut self, degrees: f64) {
+        self.position.rotation = Some(degrees);
+    }
+
+    pub fn with_rotation(mut self, degrees: f64) -> Self {
+        self.set_rotation(degrees);
+        self
+    }
+
+    /// Keeps the aspect ratio of the image within the given size, which veusz does by
+    /// default.
+    pub fn set_keep_aspect(&mut self, keep_aspect: bool) {
+        self.keep_aspect = Some(keep_aspect);
+    }
+
+    pub fn with_keep_aspect(mut self, keep_aspect: bool) -> Self {
+        self.set_keep_aspect(keep_aspect);
+        self
+    }
+
+    pub fn set_border(&mut self, border: Line) {
+        self.border = Some(border);
+    }
+
+    pub fn with_border(mut self, border: Line) -> Self {
+        self.set_border(border);
+        self
+    }
+}
+
+impl CommandLineEmbeddingInterface for ImageFile {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        cmd::Add("imagefile", &self.name).write(writer)?;
+        cmd::ToUnique(&self.name).for_call(writer, |writer| {
+            cmd::Set("filename", &self.filename).write(writer)?;
+            self.position.write(writer)?;
+
+            if let Some(keep_aspect) = &self.keep_aspect {
+                cmd::SetRaw("aspect", keep_aspect.as_veusz_api1_value_str()).write(writer)?;
+            }
+
+            if let Some(border) = &self.border {
+                border.write_as("Border", writer)?;
+            }
+
+            Ok(())
+        })
+    }
+}
+
+/// How the end of a [`LineShape`] is given.
+enum LineEnd {
+    Point(f64, f64),
+    LengthAngle(f64, f64),
+}
+
+/// A straight line with optional arrow heads. Named to not be confused with the
+/// [`Line`] style.
+pub struct LineShape {
+    name: AutoName<Self>,
+    positioning: Option<Positioning>,
+    x: f64,
+    y: f64,
+    end: LineEnd,
+    arrow_left: Option<Arrow>,
+    arrow_right: Option<Arrow>,
+    arrow_size: Option<f32>,
+    line: Option<Line>,
+    fill: Option<Fill>,
+}
+
+impl LineShape {
+    /// A line from the first to the second point.
+    pub fn points(x1: f64, y1: f64, x2: f64, y2: f64) -> Self {
+        Self::new(x1, y1, LineEnd::Point(x2, y2))
+    }
+
+    /// A line from the given point with the length as fraction of the page or graph
+    /// and the angle in degrees clockwise from the right.
+    pub fn length_angle(x: f64, y: f64, length: f64, angle: f64) -> Self {
+        Self::new(x, y, LineEnd::LengthAngle(length, angle))
+    }
+
+    fn new(x: f64, y: f64, end: LineEnd) -> Self {
+        Self {
+            name: AutoName::default(),
+            positioning: None,
+            x,
+            y,
+            end,
+            arrow_left: None,
+            arrow_right: None,
+            arrow_size: None,
+            line: None,
+            fill: None,
+        }
+    }
+
+    pub fn set_positioning(&mut self, positioning: Positioning) {
+        self.positioning = Some(positioning);
+    }
+
+    pub fn with_positioning(mut self, positioning: Positioning) -> Self {
+        self.set_positioning(positioning);
+        self
+    }
+
+    /// Head at the start of the line.
+    pub fn set_arrow_left(&mut self, arrow: Arrow) {
+        self.arrow_left = Some(arrow);
+    }
+
+    pub fn with_arrow_left(mut self, arrow: Arrow) -> Self {
+        self.set_arrow_left(arrow);
+        self
+    }
+
+    /// Head at the end of the line.
+    pub fn set_arrow_right(&mut self, arrow: Arrow) {
+        self.arrow_right = Some(arrow);
+    }
+
+    pub fn with_arrow_right(mut self, arrow: Arrow) -> Self {
+        self.set_arrow_right(arrow);
+        self
+    }
+
+    pub fn set_arrow_size(&mut self, pt: f32) {
+        self.arrow_size = Some(pt);
+    }
+
+    pub fn with_arrow_size(mut self, pt: f32) -> Self {
+        self.set_arrow_size(pt);
+        self
+    }
+
+    pub fn set_line(&mut self, line: Line) {
+        self.line = Some(line);
+    }
+
+    pub fn with_line(mut self, line: Line) -> Self {
+        self.set_line(line);
+        self
+    }
+
+    /// Fill of the arrow heads.
+    pub fn set_fill(&mut self, fill: Fill) {
+        self.fill = Some(fill);
+    }
+
+    pub fn with_fill(mut self, fill: Fill) -> Self {
+        self.set_fill(fill);
+        self
+    }
+}
+
+impl CommandLineEmbeddingInterface for LineShape {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        cmd::Add("line", &self.name).write(writer)?;
+        cmd::ToUnique(&self.name).for_call(writer, |writer| {
+            if let Some(positioning) = &self.positioning {
+                cmd::Set("positioning", positioning.as_str()).write(writer)?;
+            }
+
+            cmd::SetRaw("xPos", PyList([PyNumber(self.x)])).write(writer)?;
+            cmd::SetRaw("yPos", PyList([PyNumber(self.y)])).write(writer)?;
+
+            match self.end {
+                LineEnd::Point(x, y) => {
+                    cmd::Set("mode", "point-to-point").write(writer)?;
+                    cmd::SetRaw("xPos2", PyList([PyNumber(x)])).write(writer)?;
+                    cmd::SetRaw("yPos2", PyList([PyNumber(y)])).write(writer)?;
+                }
+                LineEnd::LengthAngle(length, angle) => {
+                    cmd::Set("mode", "length-angle").write(writer)?;
+                    cmd::SetRaw("length", PyList([PyNumber(length)])).write(writer)?;
+                    cmd::SetRaw("angle", PyList([PyNumber(angle)])).write(writer)?;
+                }
+            }
+
+            if let Some(arrow) = &self.arrow_left {
+                cmd::Set("arrowleft", arrow.as_veusz_api1_value_str()).write(writer)?;
+            }
+
+            if let Some(arrow) = &self.arrow_right {
+                cmd::Set("arrowright", arrow.as_veusz_api1_value_str()).write(writer)?;
+            }
+
+            if let Some(size) = self.arrow_size {
+                cmd::Set("arrowSize", &format!("{size}pt")).write(writer)?;
+            }
+
+            if let Some(line) = &self.line {
+                line.write_as("Line", writer)?;
+            }
+
+            if let Some(fill) = &self.fill {
+                fill.write_as("Fill", writer)?;
+            }
+
+            Ok(())
+        })
+    }
+}
+
+/// A closed polygon through the given points.
+pub struct Polygon {
+    name: AutoName<Self>,
+    positioning: Option<Positioning>,
+    points: Vec<(f64, f64)>,
+    border: Option<Line>,
+    fill: Option<Fill>,
+}
+
+impl Polygon {
+    pub fn points(points: impl IntoIterator<Item = (f64, f64)>) -> Self {
+        Self {
+            name: AutoName::default(),
+            positioning: None,
+            points: points.into_iter().collect(),
+            border: None,
+            fill: None,
+        }
+    }
+
+    pub fn set_positioning(&mut self, positioning: Positioning) {
+        self.positioning = Some(positioning);
+    }
+
+    pub fn with_positioning(mut self, positioning: Positioning) -> Self {
+        self.set_positioning(positioning);
+        self
+    }
+
+    pub fn set_border(&mut self, border: Line) {
+        self.border = Some(border);
+    }
+
+    pub fn with_border(mut self, border: Line) -> Self {
+        self.set_border(border);
+        self
+    }
+
+    pub fn set_fill(&mut self, fill: Fill) {
+        self.fill = Some(fill);
+    }
+
+    pub fn with_fill(mut self, fill: Fill) -> Self {
+        self.set_fill(fill);
+        self
+    }
+}
+
+impl CommandLineEmbeddingInterface for Polygon {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        cmd::Add("polygon", &self.name).write(writer)?;
+        cmd::ToUnique(&self.name).for_call(writer, |writer| {
+            if let Some(positioning) = &self.positioning {
+                cmd::Set("positioning", positioning.as_str()).write(writer)?;
+            }
+
+            cmd::SetRaw("xPos", PyList(self.points.iter().map(|(x, _)| PyNumber(x))))
+                .write(writer)?;
+            cmd::SetRaw("yPos", PyList(self.points.iter().map(|(_, y)| PyNumber(y))))
+                .write(writer)?;
+
+            if let Some(border) = &self.border {
+                border.write_as("Border", writer)?;
+            }
+
+            if let Some(fill) = &self.fill {
+                fill.write_as("Fill", writer)?;
+            }
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api1::written;
+
+    #[test]
+    fn rect_in_axis_coordinates() {
+        let rect = Rect::at(2.0, 0.5, 1.0, 0.25)
+            .with_positioning(Positioning::Axes)
+            .with_rotation(15.0)
+            .with_fill(Fill::default().with_color("yellow"));
+        assert_eq!(
+            "Add(u'rect', name=NAME, autoadd=False)\n\
+             To(NAME)\n\
+             Set(u'positioning', u'axes')\n\
+             Set(u'xPos', [2])\n\
+             Set(u'yPos', [0.5])\n\
+             Set(u'width', [1])\n\
+             Set(u'height', [0.25])\n\
+             Set(u'rotate', [15])\n\
+             Set(u'Fill/color', u'yellow')\n\
+             Set(u'Fill/hide', False)\n\
+             To('..')\n",
+            written(&rect, &rect.name)
+        );
+    }
+
+    #[test]
+    fn arrow_between_points() {
+        let line = LineShape::points(0.1, 0.1, 0.5, 0.4)
+            .with_arrow_right(Arrow::Arrow)
+            .with_arrow_size(6.0);
+        assert_eq!(
+            "Add(u'line', name=NAME, autoadd=False)\n\
+             To(NAME)\n\
+             Set(u'xPos', [0.1])\n\
+             Set(u'yPos', [0.1])\n\
+             Set(u'mode', u'point-to-point')\n\
+             Set(u'xPos2', [0.5])\n\
+             Set(u'yPos2', [0.4])\n\
+             Set(u'arrowright', u'arrow')\n\
+             Set(u'arrowSize', u'6pt')\n\
+             To('..')\n",
+            written(&line, &line.name)
+        );
+    }
+}
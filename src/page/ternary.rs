@@ -0,0 +1,202 @@
+use crate::api1::{cmd, AsVueszApi1ValueStr, AutoName, PyNumber};
+use crate::page::nonorth::NonOrthItem;
+use crate::CommandLineEmbeddingInterface;
+use std::io::Write;
+
+/// Whether the axes of a [`Ternary`] graph go up to 100 or to 1.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum TernaryMode {
+    Percentage,
+    Fraction,
+}
+
+impl AsVueszApi1ValueStr for TernaryMode {
+    fn as_veusz_api1_value_str(&self) -> &str {
+        match self {
+            TernaryMode::Percentage => "percentage",
+            TernaryMode::Fraction => "fraction",
+        }
+    }
+}
+
+/// The two axes of a [`Ternary`] graph its children plot their first and second
+/// coordinate on. The third component is the remainder.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum TernaryCoordinates {
+    BottomLeft,
+    BottomRight,
+    LeftBottom,
+    LeftRight,
+    RightLeft,
+    RightBottom,
+}
+
+impl AsVueszApi1ValueStr for TernaryCoordinates {
+    fn as_veusz_api1_value_str(&self) -> &str {
+        match self {
+            TernaryCoordinates::BottomLeft => "bottom-left",
+            TernaryCoordinates::BottomRight => "bottom-right",
+            TernaryCoordinates::LeftBottom => "left-bottom",
+            TernaryCoordinates::LeftRight => "left-right",
+            TernaryCoordinates::RightLeft => "right-left",
+            TernaryCoordinates::RightBottom => "right-bottom",
+        }
+    }
+}
+
+/// A triangular graph of three components adding up to a constant total.
+#[derive(Default)]
+pub struct Ternary {
+    name: AutoName<Self>,
+    mode: Option<TernaryMode>,
+    coordinates: Option<TernaryCoordinates>,
+    label_bottom: Option<String>,
+    label_left: Option<String>,
+    label_right: Option<String>,
+    origin: Option<(f64, f64)>,
+    size: Option<f64>,
+    reverse: Option<bool>,
+    items: Vec<NonOrthItem>,
+}
+
+impl Ternary {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn set_mode(&mut self, mode: TernaryMode) {
+        self.mode = Some(mode);
+    }
+
+    pub fn with_mode(mut self, mode: TernaryMode) -> Self {
+        self.set_mode(mode);
+        self
+    }
+
+    pub fn set_coordinates(&mut self, coordinates: TernaryCoordinates) {
+        self.coordinates = Some(coordinates);
+    }
+
+    pub fn with_coordinates(mut self, coordinates: TernaryCoordinates) -> Self {
+        self.set_coordinates(coordinates);
+        self
+    }
+
+    pub fn set_label_bottom(&mut self, label: impl Into<String>) {
+        self.label_bottom = Some(label.into());
+    }
+
+    pub fn with_label_bottom(mut self, label: impl Into<String>) -> Self {
+        self.set_label_bottom(label);
+        self
+    }
+
+    pub fn set_label_left(&mut self, label: impl Into<String>) {
+        self.label_left = Some(label.into());
+    }
+
+    pub fn with_label_left(mut self, label: impl Into<String>) -> Self {
+        self.set_label_left(label);
+        self
+    }
+
+    pub fn set_label_right(&mut self, label: impl Into<String>) {
+        self.label_right = Some(label.into());
+    }
+
+    pub fn with_label_right(mut self, label: impl Into<String>) -> Self {
+        self.set_label_right(label);
+        self
+    }
+
+    /// Values of the left and bottom axes at the bottom left corner, to zoom into a
+    /// part of the triangle.
+    pub fn set_origin(&mut self, left: f64, bottom: f64) {
+        self.origin = Some((left, bottom));
+    }
+
+    pub fn with_origin(mut self, left: f64, bottom: f64) -> Self {
+        self.set_origin(left, bottom);
+        self
+    }
+
+    /// Fraction of the full range shown by the axes, from 0 to 1.
+    pub fn set_size(&mut self, fraction: f64) {
+        self.size = Some(fraction);
+    }
+
+    pub fn with_size(mut self, fraction: f64) -> Self {
+        self.set_size(fraction);
+        self
+    }
+
+    /// Reverses the direction of the axes.
+    pub fn set_reverse(&mut self, reverse: bool) {
+        self.reverse = Some(reverse);
+    }
+
+    pub fn with_reverse(mut self, reverse: bool) -> Self {
+        self.set_reverse(reverse);
+        self
+    }
+
+    pub fn add(&mut self, item: impl Into<NonOrthItem>) {
+        self.items.push(item.into());
+    }
+
+    pub fn with_item(mut self, item: impl Into<NonOrthItem>) -> Self {
+        self.add(item);
+        self
+    }
+
+    pub fn with_items(mut self, items: impl IntoIterator<Item = impl Into<NonOrthItem>>) -> Self {
+        self.items.extend(items.into_iter().map(Into::into));
+        self
+    }
+}
+
+impl CommandLineEmbeddingInterface for Ternary {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        cmd::Add("ternary", &self.name).write(writer)?;
+        cmd::ToUnique(&self.name).for_call(writer, |writer| {
+            if let Some(mode) = &self.mode {
+                cmd::Set("mode", mode.as_veusz_api1_value_str()).write(writer)?;
+            }
+
+            if let Some(coordinates) = &self.coordinates {
+                cmd::Set("coords", coordinates.as_veusz_api1_value_str()).write(writer)?;
+            }
+
+            if let Some(label) = &self.label_bottom {
+                cmd::Set("labelbottom", label).write(writer)?;
+            }
+
+            if let Some(label) = &self.label_left {
+                cmd::Set("labelleft", label).write(writer)?;
+            }
+
+            if let Some(label) = &self.label_right {
+                cmd::Set("labelright", label).write(writer)?;
+            }
+
+            if let Some((left, bottom)) = self.origin {
+                cmd::SetRaw("originleft", PyNumber(left)).write(writer)?;
+                cmd::SetRaw("originbottom", PyNumber(bottom)).write(writer)?;
+            }
+
+            if let Some(size) = self.size {
+                cmd::SetRaw("fracsize", PyNumber(size)).write(writer)?;
+            }
+
+            if let Some(reverse) = &self.reverse {
+                cmd::SetRaw("reverse", reverse.as_veusz_api1_value_str()).write(writer)?;
+            }
+
+            for item in &self.items {
+                item.write(writer)?;
+            }
+
+            Ok(())
+        })
+    }
+}
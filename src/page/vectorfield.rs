@@ -0,0 +1,205 @@
+use crate::api1::{cmd, AsVueszApi1ValueStr, AutoName};
+use crate::page::AxisBinding;
+use crate::style::arrow::Arrow;
+use crate::style::fill::Fill;
+use crate::style::line::Line;
+use crate::CommandLineEmbeddingInterface;
+use std::io::Write;
+
+/// How the two datasets of a [`VectorField`] describe the vectors.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum VectorMode {
+    /// The x and y components
+    Cartesian,
+    /// The length and the angle in radians
+    Polar,
+}
+
+impl AsVueszApi1ValueStr for VectorMode {
+    fn as_veusz_api1_value_str(&self) -> &str {
+        match self {
+            VectorMode::Cartesian => "cartesian",
+            VectorMode::Polar => "polar",
+        }
+    }
+}
+
+/// Arrows drawn on the grid of two, equally shaped two dimensional datasets.
+pub struct VectorField {
+    name: AutoName<Self>,
+    axes: AxisBinding,
+    data1: String,
+    data2: String,
+    mode: VectorMode,
+    base_length: Option<f32>,
+    arrow_size: Option<f32>,
+    scale_arrow: Option<bool>,
+    arrow_front: Option<Arrow>,
+    arrow_back: Option<Arrow>,
+    line: Option<Line>,
+    fill: Option<Fill>,
+}
+
+axis_binding!(VectorField);
+
+impl VectorField {
+    pub fn cartesian(x: impl Into<String>, y: impl Into<String>) -> Self {
+        Self::new(x.into(), y.into(), VectorMode::Cartesian)
+    }
+
+    pub fn polar(r: impl Into<String>, theta: impl Into<String>) -> Self {
+        Self::new(r.into(), theta.into(), VectorMode::Polar)
+    }
+
+    fn new(data1: String, data2: String, mode: VectorMode) -> Self {
+        Self {
+            name: AutoName::default(),
+            axes: AxisBinding::default(),
+            data1,
+            data2,
+            mode,
+            base_length: None,
+            arrow_size: None,
+            scale_arrow: None,
+            arrow_front: None,
+            arrow_back: None,
+            line: None,
+            fill: None,
+        }
+    }
+
+    /// Length of a vector of length one.
+    pub fn set_base_length(&mut self, pt: f32) {
+        self.base_length = Some(pt);
+    }
+
+    pub fn with_base_length(mut self, pt: f32) -> Self {
+        self.set_base_length(pt);
+        self
+    }
+
+    pub fn set_arrow_size(&mut self, pt: f32) {
+        self.arrow_size = Some(pt);
+    }
+
+    pub fn with_arrow_size(mut self, pt: f32) -> Self {
+        self.set_arrow_size(pt);
+        self
+    }
+
+    /// Scale the size of the arrow heads with the length of the vectors.
+    pub fn set_scale_arrow(&mut self, scale: bool) {
+        self.scale_arrow = Some(scale);
+    }
+
+    pub fn with_scale_arrow(mut self, scale: bool) -> Self {
+        self.set_scale_arrow(scale);
+        self
+    }
+
+    pub fn set_arrow_front(&mut self, arrow: Arrow) {
+        self.arrow_front = Some(arrow);
+    }
+
+    pub fn with_arrow_front(mut self, arrow: Arrow) -> Self {
+        self.set_arrow_front(arrow);
+        self
+    }
+
+    pub fn set_arrow_back(&mut self, arrow: Arrow) {
+        self.arrow_back = Some(arrow);
+    }
+
+    pub fn with_arrow_back(mut self, arrow: Arrow) -> Self {
+        self.set_arrow_back(arrow);
+        self
+    }
+
+    pub fn set_line(&mut self, line: Line) {
+        self.line = Some(line);
+    }
+
+    pub fn with_line(mut self, line: Line) -> Self {
+        self.set_line(line);
+        self
+    }
+
+    /// Fill of the arrow heads.
+    pub fn set_fill(&mut self, fill: Fill) {
+        self.fill = Some(fill);
+    }
+
+    pub fn with_fill(mut self, fill: Fill) -> Self {
+        self.set_fill(fill);
+        self
+    }
+}
+
+impl CommandLineEmbeddingInterface for VectorField {
+    fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        cmd::Add("vectorfield", &self.name).write(writer)?;
+        cmd::ToUnique(&self.name).for_call(writer, |writer| {
+            cmd::Set("data1", &self.data1).write(writer)?;
+            cmd::Set("data2", &self.data2).write(writer)?;
+            cmd::Set("mode", self.mode.as_veusz_api1_value_str()).write(writer)?;
+
+            if let Some(length) = self.base_length {
+                cmd::Set("baselength", &format!("{length}pt")).write(writer)?;
+            }
+
+            if let Some(size) = self.arrow_size {
+                cmd::Set("arrowsize", &format!("{size}pt")).write(writer)?;
+            }
+
+            if let Some(scale) = &self.scale_arrow {
+                cmd::SetRaw("scalearrow", scale.as_veusz_api1_value_str()).write(writer)?;
+            }
+
+            if let Some(arrow) = &self.arrow_front {
+                cmd::Set("arrowfront", arrow.as_veusz_api1_value_str()).write(writer)?;
+            }
+
+            if let Some(arrow) = &self.arrow_back {
+                cmd::Set("arrowback", arrow.as_veusz_api1_value_str()).write(writer)?;
+            }
+
+            if let Some(line) = &self.line {
+                line.write_as("Line", writer)?;
+            }
+
+            if let Some(fill) = &self.fill {
+                fill.write_as("Fill", writer)?;
+            }
+
+            self.axes.write(writer)?;
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api1::written;
+
+    #[test]
+    fn polar_vectors() {
+        let field = VectorField::polar("speed", "direction")
+            .with_base_length(5.0)
+            .with_arrow_front(Arrow::ArrowNarrow)
+            .with_scale_arrow(false);
+        assert_eq!(
+            "Add(u'vectorfield', name=NAME, autoadd=False)\n\
+             To(NAME)\n\
+             Set(u'data1', u'speed')\n\
+             Set(u'data2', u'direction')\n\
+             Set(u'mode', u'polar')\n\
+             Set(u'baselength', u'5pt')\n\
+             Set(u'scalearrow', False)\n\
+             Set(u'arrowfront', u'arrownarrow')\n\
+             To('..')\n",
+            written(&field, &field.name)
+        );
+    }
+}
@@ -0,0 +1,435 @@
+use crate::api1::{cmd, CommandLineEmbeddingInterface, PyStr};
+use crate::launcher::{Output, Process};
+use crate::value::Value;
+use crate::{Error, Result, Veusz};
+use std::fmt::Display;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::process::{ChildStdin, Command, ExitStatus, Stdio};
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::time::Duration;
+
+const REPLY_PREFIX: &str = "veusz-rs-reply: ";
+const ERROR_PREFIX: &str = "veusz-rs-error: ";
+
+/// Defines the python helpers used to answer queries. Query results are converted to
+/// plain python values and written as `repr()` in a single, prefixed line to stdout.
+const REPLY_HELPER: &str = concat!(
+    "exec(\"",
+    "def __veusz_rs_plain(v):\\n",
+    " if hasattr(v, 'tolist'): return v.tolist()\\n",
+    " if isinstance(v, (list, tuple)): return type(v)(__veusz_rs_plain(x) for x in v)\\n",
+    " if isinstance(v, dict): return dict((k, __veusz_rs_plain(x)) for k, x in v.items())\\n",
+    " return v\\n",
+    "def __veusz_rs_reply(f):\\n",
+    " try: v = f()\\n",
+    " except Exception as e:\\n",
+    "  print('veusz-rs-error: ' + repr(str(e)), flush=True)\\n",
+    "  return\\n",
+    " print('veusz-rs-reply: ' + repr(__veusz_rs_plain(v)), flush=True)\\n",
+    "\")"
+);
+
+/// The values of a numeric dataset as returned by [`VeuszSession::get_data`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DatasetValues {
+    pub values: Vec<f64>,
+    pub symerr: Option<Vec<f64>>,
+    pub poserr: Option<Vec<f64>>,
+    pub negerr: Option<Vec<f64>>,
+}
+
+impl DatasetValues {
+    /// Converts the `(data, serr, perr, nerr)` tuple returned by `GetData`.
+    pub(crate) fn from_reply(reply: Value) -> Result<Self> {
+        let Value::Tuple(mut columns) = reply else {
+            return Err(Error::Protocol(format!(
+                "expected a tuple of dataset columns, got {reply:?}"
+            )));
+        };
+        if columns.len() != 4 {
+            return Err(Error::Protocol(format!(
+                "expected 4 dataset columns, got {}",
+                columns.len()
+            )));
+        }
+
+        let optional = |value: Value| match value {
+            Value::None => Ok(None),
+            value => value.into_f64s().map(Some),
+        };
+        let negerr = optional(columns.remove(3))?;
+        let poserr = optional(columns.remove(2))?;
+        let symerr = optional(columns.remove(1))?;
+        let values = columns.remove(0).into_f64s()?;
+
+        Ok(Self {
+            values,
+            symerr,
+            poserr,
+            negerr,
+        })
+    }
+}
+
+/// The outcome of a fit as returned by [`VeuszSession::fit`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FitResult {
+    /// The fitted value of each parameter
+    pub values: Vec<(String, f64)>,
+    pub chi2: f64,
+    /// Degrees of freedom
+    pub dof: i64,
+    pub reduced_chi2: f64,
+}
+
+/// A running `veusz --listen` process which accepts further commands after the initial
+/// document and answers queries through its stdout.
+pub struct VeuszSession {
+    process: Process,
+    stdin: BufWriter<ChildStdin>,
+    /// The lines of stdout, read by a separate thread so that queries can time out
+    lines: Receiver<std::io::Result<String>>,
+    timeout: Option<Duration>,
+}
+
+impl VeuszSession {
+    /// Starts `veusz --listen` with the launcher of the given document and sends it.
+    pub fn open(veusz: &Veusz) -> Result<Self> {
+        let launcher = veusz.launcher.clone().with_stdout(Output::Inherit);
+        let mut command = launcher.command(&["--listen"]);
+        command.stdout(Stdio::piped());
+        let mut session = Self::start(launcher.spawn(command)?)?;
+        session.send(veusz)?;
+        Ok(session)
+    }
+
+    /// Starts a session with the given command, which must speak the line protocol of
+    /// `veusz --listen` on its stdin and stdout.
+    pub fn spawn(mut command: Command) -> Result<Self> {
+        let process = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .map_err(|e| Error::spawn(command.get_program(), e))?;
+        Self::start(Process::from_child(process))
+    }
+
+    fn start(mut process: Process) -> Result<Self> {
+        let stdin = process.take_stdin()?;
+        let stdout = process.take_stdout()?;
+        let (sender, lines) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        let timeout = process.timeout();
+        let mut session = Self {
+            process,
+            stdin: BufWriter::new(stdin),
+            lines,
+            timeout,
+        };
+        session.command(REPLY_HELPER)?;
+        Ok(session)
+    }
+
+    /// Maximum time to wait for the reply to a query, defaults to the timeout of the
+    /// [`Launcher`](crate::launcher::Launcher). The session should be closed after a
+    /// query timed out, because a late reply would be taken for the one of the next query.
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = Some(timeout);
+    }
+
+    /// Sends the data, pages and exports of the given document.
+    pub fn send(&mut self, veusz: &Veusz) -> Result<()> {
+        veusz.write(&mut self.stdin)?;
+        self.stdin.flush()?;
+        Ok(())
+    }
+
+    pub fn set(&mut self, path: &str, value: &str) -> Result<()> {
+        cmd::Set(path, value).write(&mut self.stdin)?;
+        self.stdin.flush()?;
+        Ok(())
+    }
+
+    /// The value is sent as is and must therefore be a valid python expression.
+    pub fn set_raw(&mut self, path: &str, value: impl Display) -> Result<()> {
+        cmd::SetRaw(path, value).write(&mut self.stdin)?;
+        self.stdin.flush()?;
+        Ok(())
+    }
+
+    /// Sends a single, raw command line.
+    pub fn command(&mut self, command: &str) -> Result<()> {
+        writeln!(self.stdin, "{command}")?;
+        self.stdin.flush()?;
+        Ok(())
+    }
+
+    /// Evaluates the given python expression in veusz and returns its result.
+    pub fn query(&mut self, expression: &str) -> Result<Value> {
+        self.command(&format!("__veusz_rs_reply(lambda: {expression})"))?;
+
+        loop {
+            let line = match self.timeout {
+                None => self
+                    .lines
+                    .recv()
+                    .map_err(|_| RecvTimeoutError::Disconnected),
+                Some(timeout) => self.lines.recv_timeout(timeout),
+            };
+            let line = match line {
+                Ok(line) => line?,
+                Err(RecvTimeoutError::Timeout) => {
+                    return Err(Error::Timeout(self.timeout.unwrap_or_default()))
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(Error::Protocol(
+                        "veusz closed stdout before replying".into(),
+                    ))
+                }
+            };
+            let line = line.trim_end_matches('\r');
+            if let Some(reply) = line.strip_prefix(REPLY_PREFIX) {
+                return Value::parse_python_literal(reply);
+            } else if let Some(error) = line.strip_prefix(ERROR_PREFIX) {
+                return Err(Error::Command(
+                    Value::parse_python_literal(error)?
+                        .as_str()
+                        .unwrap_or(error)
+                        .to_string(),
+                ));
+            }
+        }
+    }
+
+    /// The value of the setting at the given path.
+    pub fn get(&mut self, path: &str) -> Result<Value> {
+        self.query(&format!("Get({})", PyStr(path)))
+    }
+
+    /// The names of the child widgets of the widget at the given path, optionally
+    /// restricted to widgets of the given type.
+    pub fn get_children(&mut self, path: &str, widget_type: Option<&str>) -> Result<Vec<String>> {
+        match widget_type {
+            Some(widget_type) => self.query(&format!(
+                "GetChildren(where={}, widgettype={})",
+                PyStr(path),
+                PyStr(widget_type)
+            )),
+            None => self.query(&format!("GetChildren(where={})", PyStr(path))),
+        }?
+        .into_strings()
+    }
+
+    pub fn widget_type(&mut self, path: &str) -> Result<String> {
+        match self.query(&format!("WidgetType({})", PyStr(path)))? {
+            Value::Str(widget_type) => Ok(widget_type),
+            other => Err(Error::Protocol(format!(
+                "expected a widget type, got {other:?}"
+            ))),
+        }
+    }
+
+    /// The names of all datasets of the document.
+    pub fn get_datasets(&mut self) -> Result<Vec<String>> {
+        self.query("GetDatasets()")?.into_strings()
+    }
+
+    /// The values and errors of a numeric, one dimensional dataset.
+    pub fn get_data(&mut self, name: &str) -> Result<DatasetValues> {
+        let reply = self.query(&format!("GetData({})", PyStr(name)))?;
+        DatasetValues::from_reply(reply)
+    }
+
+    /// Runs the fit of the fit widget at the given path, like `/page1/graph1/fit1`, and
+    /// returns the fitted parameters, which are also applied to the widget.
+    pub fn fit(&mut self, path: &str) -> Result<FitResult> {
+        self.query(&format!("Action(u'fit', widget={})", PyStr(path)))?;
+
+        let values = match self.get(&format!("{path}/values"))? {
+            Value::Dict(entries) => entries
+                .into_iter()
+                .map(|(name, value)| match (name, value.as_f64()) {
+                    (Value::Str(name), Some(value)) => Ok((name, value)),
+                    (name, _) => Err(Error::Protocol(format!(
+                        "expected a fitted value for {name:?}, got {value:?}"
+                    ))),
+                })
+                .collect::<Result<Vec<_>>>()?,
+            other => {
+                return Err(Error::Protocol(format!(
+                    "expected the fitted values, got {other:?}"
+                )))
+            }
+        };
+        let mut number = |setting: &str| {
+            self.get(&format!("{path}/{setting}"))?
+                .as_f64()
+                .ok_or_else(|| Error::Protocol(format!("expected a number for {setting}")))
+        };
+
+        Ok(FitResult {
+            values,
+            chi2: number("chi2")?,
+            dof: number("dof")? as i64,
+            reduced_chi2: number("redchi2")?,
+        })
+    }
+
+    /// Reads the linked datasets again from their files and returns the names of the
+    /// reloaded datasets.
+    pub fn reload_data(&mut self) -> Result<Vec<String>> {
+        match self.query("ReloadData()")? {
+            Value::Tuple(mut reply) if !reply.is_empty() => reply.remove(0).into_strings(),
+            other => Err(Error::Protocol(format!(
+                "expected the reloaded datasets, got {other:?}"
+            ))),
+        }
+    }
+
+    /// Waits for veusz to exit, for example because the user closed the window.
+    pub fn wait(mut self) -> Result<ExitStatus> {
+        self.process.wait()
+    }
+
+    /// Asks veusz to quit and waits for it to exit.
+    pub fn close(mut self) -> Result<ExitStatus> {
+        self.command("Quit()")?;
+        drop(self.stdin);
+        self.process.wait()
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::data::Data;
+
+    /// Stands in for `veusz --listen` by answering the queries issued by the session
+    /// with canned replies.
+    const STAND_IN: &str = r#"
+        while IFS= read -r line; do
+            case "$line" in
+                *"GetDatasets()"*)
+                    echo "some unrelated output"
+                    echo "veusz-rs-reply: ['x-data', u'O\\'Brien']" ;;
+                *"WidgetType(u'/page1/graph1')"*)
+                    echo "veusz-rs-reply: 'graph'" ;;
+                *"GetChildren(where=u'/page1', widgettype=u'graph')"*)
+                    echo "veusz-rs-reply: ['graph1']" ;;
+                *"GetData(u'x-data')"*)
+                    echo "veusz-rs-reply: ([1.0, 2.5, nan], [0.1, 0.2, 0.3], None, None)" ;;
+                *"Get(u'/page1/graph1/x/min')"*)
+                    echo "veusz-rs-reply: -1e-05" ;;
+                *"ReloadData()"*)
+                    echo "veusz-rs-reply: (['a', 'b'], {})" ;;
+                *"Action(u'fit', widget=u'/page1/graph1/fit1')"*)
+                    echo "veusz-rs-reply: None" ;;
+                *"Get(u'/page1/graph1/fit1/values')"*)
+                    echo "veusz-rs-reply: {'a': 1.5, 'b': -2}" ;;
+                *"Get(u'/page1/graph1/fit1/chi2')"*)
+                    echo "veusz-rs-reply: 3.2" ;;
+                *"Get(u'/page1/graph1/fit1/dof')"*)
+                    echo "veusz-rs-reply: 8" ;;
+                *"Get(u'/page1/graph1/fit1/redchi2')"*)
+                    echo "veusz-rs-reply: 0.4" ;;
+                *"Get(u'/hanging')"*)
+                    ;;
+                *"Get(u'/missing')"*)
+                    echo "veusz-rs-error: 'widget not found'" ;;
+                *"SetData(u'y-data'"*)
+                    received="True" ;;
+                *"Get(u'/received')"*)
+                    echo "veusz-rs-reply: ${received:-False}" ;;
+                "Quit()")
+                    exit 0 ;;
+            esac
+        done
+    "#;
+
+    fn stand_in() -> VeuszSession {
+        let mut command = Command::new("sh");
+        command.arg("-c").arg(STAND_IN);
+        VeuszSession::spawn(command).unwrap()
+    }
+
+    #[test]
+    fn queries_return_typed_values() {
+        let mut session = stand_in();
+        assert_eq!(
+            vec!["x-data".to_string(), "O'Brien".to_string()],
+            session.get_datasets().unwrap()
+        );
+        assert_eq!("graph", session.widget_type("/page1/graph1").unwrap());
+        assert_eq!(
+            vec!["graph1".to_string()],
+            session.get_children("/page1", Some("graph")).unwrap()
+        );
+        assert_eq!(
+            Value::Float(-1e-05),
+            session.get("/page1/graph1/x/min").unwrap()
+        );
+
+        let data = session.get_data("x-data").unwrap();
+        assert_eq!(&[1.0, 2.5], &data.values[..2]);
+        assert!(data.values[2].is_nan());
+        assert_eq!(Some(vec![0.1, 0.2, 0.3]), data.symerr);
+        assert_eq!(None, data.poserr);
+        assert_eq!(None, data.negerr);
+
+        assert_eq!(
+            vec!["a".to_string(), "b".to_string()],
+            session.reload_data().unwrap()
+        );
+
+        assert!(session.close().unwrap().success());
+    }
+
+    #[test]
+    fn fit_results() {
+        let mut session = stand_in();
+        assert_eq!(
+            FitResult {
+                values: vec![("a".to_string(), 1.5), ("b".to_string(), -2.0)],
+                chi2: 3.2,
+                dof: 8,
+                reduced_chi2: 0.4,
+            },
+            session.fit("/page1/graph1/fit1").unwrap()
+        );
+        session.close().unwrap();
+    }
+
+    #[test]
+    fn errors_are_reported() {
+        let mut session = stand_in();
+        match session.get("/missing") {
+            Err(Error::Command(message)) => assert_eq!("widget not found", message),
+            other => panic!("unexpected result: {other:?}"),
+        }
+        session.close().unwrap();
+    }
+
+    #[test]
+    fn queries_time_out() {
+        let mut session = stand_in();
+        session.set_timeout(Duration::from_millis(50));
+        assert!(matches!(session.get("/hanging"), Err(Error::Timeout(_))));
+        session.close().unwrap();
+    }
+
+    #[test]
+    fn commands_after_initial_document() {
+        let mut session = stand_in();
+        session
+            .send(&Veusz::default().with_data(Data::new("y-data", [1, 2, 3])))
+            .unwrap();
+        assert_eq!(Value::Bool(true), session.get("/received").unwrap());
+        session.close().unwrap();
+    }
+}
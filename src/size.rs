@@ -1,12 +1,14 @@
+use std::fmt::{Display, Formatter};
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum SizeUnit {
     Centimeter(f64),
 }
 
-impl ToString for SizeUnit {
-    fn to_string(&self) -> String {
+impl Display for SizeUnit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
-            SizeUnit::Centimeter(value) => format!("{value} cm"),
+            SizeUnit::Centimeter(value) => write!(f, "{value} cm"),
         }
     }
 }
@@ -0,0 +1,39 @@
+use crate::api1::AsVueszApi1ValueStr;
+
+/// The end of a line, like the head of a vector or of a line shape.
+#[derive(Copy, Clone, PartialOrd, PartialEq)]
+pub enum Arrow {
+    None,
+    Arrow,
+    ArrowNarrow,
+    ArrowTriangle,
+    ArrowReverse,
+    LineArrow,
+    LineArrowReverse,
+    Bar,
+    LineCross,
+    Asterisk,
+    Circle,
+    Square,
+    Diamond,
+}
+
+impl AsVueszApi1ValueStr for Arrow {
+    fn as_veusz_api1_value_str(&self) -> &str {
+        match self {
+            Arrow::None => "none",
+            Arrow::Arrow => "arrow",
+            Arrow::ArrowNarrow => "arrownarrow",
+            Arrow::ArrowTriangle => "arrowtriangle",
+            Arrow::ArrowReverse => "arrowreverse",
+            Arrow::LineArrow => "linearrow",
+            Arrow::LineArrowReverse => "linearrowreverse",
+            Arrow::Bar => "bar",
+            Arrow::LineCross => "linecross",
+            Arrow::Asterisk => "asterisk",
+            Arrow::Circle => "circle",
+            Arrow::Square => "square",
+            Arrow::Diamond => "diamond",
+        }
+    }
+}
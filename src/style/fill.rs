@@ -0,0 +1,139 @@
+use crate::api1::{cmd, AsVueszApi1ValueStr, PyStr};
+use crate::style::ColorName;
+use std::io::Write;
+
+/// https://github.com/veusz/veusz/blob/b06b5da124c7d712bafadfc86f75f474a655625c/veusz/setting/setting.py#L1478
+#[derive(Copy, Clone, PartialOrd, PartialEq)]
+pub enum FillStyle {
+    Solid,
+    Horizontal,
+    Vertical,
+    Cross,
+    ForwardDiagonals,
+    BackwardDiagonals,
+    DiagonalCross,
+    Forward2,
+    Backward2,
+    Forward3,
+    Backward3,
+    Forward4,
+    Backward4,
+    Forward5,
+    Backward5,
+}
+
+impl AsVueszApi1ValueStr for FillStyle {
+    fn as_veusz_api1_value_str(&self) -> &str {
+        match self {
+            FillStyle::Solid => "solid",
+            FillStyle::Horizontal => "horizontal",
+            FillStyle::Vertical => "vertical",
+            FillStyle::Cross => "cross",
+            FillStyle::ForwardDiagonals => "forward diagonals",
+            FillStyle::BackwardDiagonals => "backward diagonals",
+            FillStyle::DiagonalCross => "diagonal cross",
+            FillStyle::Forward2 => "forward 2",
+            FillStyle::Backward2 => "backward 2",
+            FillStyle::Forward3 => "forward 3",
+            FillStyle::Backward3 => "backward 3",
+            FillStyle::Forward4 => "forward 4",
+            FillStyle::Backward4 => "backward 4",
+            FillStyle::Forward5 => "forward 5",
+            FillStyle::Backward5 => "backward 5",
+        }
+    }
+}
+
+/// The fill of an area, like `FillBelow` of a function or `Fill` of a shape.
+#[derive(Default)]
+pub struct Fill {
+    color: Option<String>,
+    style: Option<FillStyle>,
+    transparency: Option<u8>,
+    hide: Option<bool>,
+}
+
+impl Fill {
+    pub fn set_color(&mut self, color: impl Into<String>) {
+        self.color = Some(color.into());
+    }
+
+    pub fn with_color(mut self, color: impl Into<String>) -> Self {
+        self.set_color(color);
+        self
+    }
+
+    pub fn set_color_by_name(&mut self, color_name: ColorName) {
+        self.color = Some(color_name.as_veusz_api1_value_str().to_string());
+    }
+
+    pub fn with_color_by_name(mut self, color_name: ColorName) -> Self {
+        self.set_color_by_name(color_name);
+        self
+    }
+
+    pub fn set_style(&mut self, style: FillStyle) {
+        self.style = Some(style);
+    }
+
+    pub fn with_style(mut self, style: FillStyle) -> Self {
+        self.set_style(style);
+        self
+    }
+
+    pub fn set_transparency(&mut self, transparency: u8) {
+        self.transparency = Some(transparency);
+    }
+
+    pub fn with_transparency(mut self, transparency: u8) -> Self {
+        self.set_transparency(transparency);
+        self
+    }
+
+    pub fn set_hide(&mut self, hide: bool) {
+        self.hide = Some(hide);
+    }
+
+    pub fn with_hide(mut self, hide: bool) -> Self {
+        self.set_hide(hide);
+        self
+    }
+
+    /// Formats the fill as `(style, color, hide)` item of a list of fills, like the fills
+    /// between the levels of a contour. Unset values default to a solid grey.
+    pub(crate) fn fill_set_item(&self) -> String {
+        format!(
+            "({}, {}, {})",
+            PyStr(
+                self.style
+                    .unwrap_or(FillStyle::Solid)
+                    .as_veusz_api1_value_str()
+            ),
+            PyStr(self.color.as_deref().unwrap_or("grey")),
+            self.hide.unwrap_or(false).as_veusz_api1_value_str()
+        )
+    }
+
+    /// Writes the settings below the given settings group, like `FillBelow`. Unlike
+    /// other settings, the fill is shown unless it is hidden explicitly, as most fills
+    /// are hidden by default in veusz.
+    pub(crate) fn write_as<W: Write>(&self, group: &str, writer: &mut W) -> std::io::Result<()> {
+        if let Some(color) = &self.color {
+            cmd::Set(&format!("{group}/color"), color).write(writer)?;
+        }
+
+        if let Some(style) = &self.style {
+            cmd::Set(&format!("{group}/style"), style.as_veusz_api1_value_str()).write(writer)?;
+        }
+
+        if let Some(transparency) = &self.transparency {
+            cmd::SetRaw(&format!("{group}/transparency"), transparency).write(writer)?;
+        }
+
+        cmd::SetRaw(
+            &format!("{group}/hide"),
+            self.hide.unwrap_or(false).as_veusz_api1_value_str(),
+        )
+        .write(writer)
+    }
+}
@@ -1,4 +1,6 @@
-use crate::api1::AsVueszApi1ValueStr;
+use crate::api1::{cmd, AsVueszApi1ValueStr, PyStr};
+use crate::style::ColorName;
+use std::io::Write;
 
 /// https://github.com/veusz/veusz/blob/b06b5da124c7d712bafadfc86f75f474a655625c/veusz/setting/setting.py#L1424
 #[derive(Copy, Clone, PartialOrd, PartialEq)]
@@ -51,3 +53,112 @@ impl AsVueszApi1ValueStr for LineStyle {
         }
     }
 }
+
+/// A line of a widget other than the [`PlotLine`](crate::style::plot::PlotLine), like
+/// the `Border` of a box or the `Whisker` of a box plot.
+#[derive(Default)]
+pub struct Line {
+    color: Option<String>,
+    width: Option<f32>,
+    style: Option<LineStyle>,
+    transparency: Option<u8>,
+    hide: Option<bool>,
+}
+
+impl Line {
+    pub fn set_color(&mut self, color: impl Into<String>) {
+        self.color = Some(color.into());
+    }
+
+    pub fn with_color(mut self, color: impl Into<String>) -> Self {
+        self.set_color(color);
+        self
+    }
+
+    pub fn set_color_by_name(&mut self, color_name: ColorName) {
+        self.color = Some(color_name.as_veusz_api1_value_str().to_string());
+    }
+
+    pub fn with_color_by_name(mut self, color_name: ColorName) -> Self {
+        self.set_color_by_name(color_name);
+        self
+    }
+
+    pub fn set_width(&mut self, pt: f32) {
+        self.width = Some(pt);
+    }
+
+    pub fn with_width(mut self, pt: f32) -> Self {
+        self.set_width(pt);
+        self
+    }
+
+    pub fn set_style(&mut self, style: LineStyle) {
+        self.style = Some(style);
+    }
+
+    pub fn with_style(mut self, style: LineStyle) -> Self {
+        self.set_style(style);
+        self
+    }
+
+    pub fn set_transparency(&mut self, transparency: u8) {
+        self.transparency = Some(transparency);
+    }
+
+    pub fn with_transparency(mut self, transparency: u8) -> Self {
+        self.set_transparency(transparency);
+        self
+    }
+
+    pub fn set_hide(&mut self, hide: bool) {
+        self.hide = Some(hide);
+    }
+
+    pub fn with_hide(mut self, hide: bool) -> Self {
+        self.set_hide(hide);
+        self
+    }
+
+    /// Formats the line as `(style, width, color, hide)` item of a list of lines, like
+    /// the lines of the levels of a contour. Unset values default to a solid, black
+    /// line of 1pt.
+    pub(crate) fn line_set_item(&self) -> String {
+        format!(
+            "({}, {}, {}, {})",
+            PyStr(
+                self.style
+                    .unwrap_or(LineStyle::Solid)
+                    .as_veusz_api1_value_str()
+            ),
+            PyStr(&format!("{}pt", self.width.unwrap_or(1.0))),
+            PyStr(self.color.as_deref().unwrap_or("black")),
+            self.hide.unwrap_or(false).as_veusz_api1_value_str()
+        )
+    }
+
+    /// Writes the settings below the given settings group, like `Border`.
+    pub(crate) fn write_as<W: Write>(&self, group: &str, writer: &mut W) -> std::io::Result<()> {
+        if let Some(color) = &self.color {
+            cmd::Set(&format!("{group}/color"), color).write(writer)?;
+        }
+
+        if let Some(width) = &self.width {
+            cmd::Set(&format!("{group}/width"), &format!("{width}pt")).write(writer)?;
+        }
+
+        if let Some(style) = &self.style {
+            cmd::Set(&format!("{group}/style"), style.as_veusz_api1_value_str()).write(writer)?;
+        }
+
+        if let Some(transparency) = &self.transparency {
+            cmd::SetRaw(&format!("{group}/transparency"), transparency).write(writer)?;
+        }
+
+        if let Some(hide) = &self.hide {
+            cmd::SetRaw(&format!("{group}/hide"), hide.as_veusz_api1_value_str()).write(writer)?;
+        }
+
+        Ok(())
+    }
+}
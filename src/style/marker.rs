@@ -8,6 +8,35 @@ use std::io::Write;
 pub enum Marker {
     None,
     Circle,
+    Square,
+    Diamond,
+    Cross,
+    Plus,
+    Star,
+    Triangle,
+    Dot,
+    LineCross,
+    LineHorizontal,
+    LineVertical,
+}
+
+impl AsVueszApi1ValueStr for Marker {
+    fn as_veusz_api1_value_str(&self) -> &str {
+        match self {
+            Marker::None => "none",
+            Marker::Circle => "circle",
+            Marker::Square => "square",
+            Marker::Diamond => "diamond",
+            Marker::Cross => "cross",
+            Marker::Plus => "plus",
+            Marker::Star => "star",
+            Marker::Triangle => "triangle",
+            Marker::Dot => "dot",
+            Marker::LineCross => "linecross",
+            Marker::LineHorizontal => "linehorz",
+            Marker::LineVertical => "linevert",
+        }
+    }
 }
 
 #[derive(Default)]
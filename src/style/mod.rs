@@ -1,7 +1,9 @@
-use crate::api1::{cmd, AsVueszApi1ValueStr};
+use crate::api1::{cmd, AsVueszApi1ValueStr, PyNumber};
 use crate::CommandLineEmbeddingInterface;
 use std::io::Write;
 
+pub mod arrow;
+pub mod fill;
 pub mod line;
 pub mod marker;
 pub mod plot;
@@ -185,13 +187,61 @@ impl CommandLineEmbeddingInterface for Color {
         cmd::Set("Color/points", &self.points).write(writer)?;
 
         if let Some(min) = &self.min {
-            cmd::SetRaw("Color/min", min).write(writer)?;
+            cmd::SetRaw("Color/min", PyNumber(min)).write(writer)?;
         }
 
         if let Some(max) = &self.max {
-            cmd::SetRaw("Color/max", max).write(writer)?;
+            cmd::SetRaw("Color/max", PyNumber(max)).write(writer)?;
         }
 
         Ok(())
     }
 }
+
+/// How errors of the plotted datasets are shown.
+#[derive(Copy, Clone, PartialOrd, PartialEq)]
+pub enum ErrorStyle {
+    None,
+    Bar,
+    BarEnds,
+    Box,
+    Diamond,
+    Curve,
+    BarBox,
+    BarDiamond,
+    BarCurve,
+    BoxFill,
+    DiamondFill,
+    CurveFill,
+    FillVertical,
+    FillHorizontal,
+    LineVertical,
+    LineHorizontal,
+    LineVerticalBar,
+    LineHorizontalBar,
+}
+
+impl AsVueszApi1ValueStr for ErrorStyle {
+    fn as_veusz_api1_value_str(&self) -> &str {
+        match self {
+            ErrorStyle::None => "none",
+            ErrorStyle::Bar => "bar",
+            ErrorStyle::BarEnds => "barends",
+            ErrorStyle::Box => "box",
+            ErrorStyle::Diamond => "diamond",
+            ErrorStyle::Curve => "curve",
+            ErrorStyle::BarBox => "barbox",
+            ErrorStyle::BarDiamond => "bardiamond",
+            ErrorStyle::BarCurve => "barcurve",
+            ErrorStyle::BoxFill => "boxfill",
+            ErrorStyle::DiamondFill => "diamondfill",
+            ErrorStyle::CurveFill => "curvefill",
+            ErrorStyle::FillVertical => "fillvert",
+            ErrorStyle::FillHorizontal => "fillhorz",
+            ErrorStyle::LineVertical => "linevert",
+            ErrorStyle::LineHorizontal => "linehorz",
+            ErrorStyle::LineVerticalBar => "linevertbar",
+            ErrorStyle::LineHorizontalBar => "linehorzbar",
+        }
+    }
+}
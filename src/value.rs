@@ -0,0 +1,351 @@
+use crate::{Error, Result};
+
+/// A python value as returned by veusz for queries such as `Get` or `GetData`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    None,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+    Bytes(Vec<u8>),
+    List(Vec<Value>),
+    Tuple(Vec<Value>),
+    Dict(Vec<(Value, Value)>),
+    /// A one dimensional numpy array of numbers, transferred in binary form
+    Array(Vec<f64>),
+    /// Any other python object, described by its class (`module.name`), the arguments
+    /// it is constructed from and its optional state
+    Object {
+        class: String,
+        args: Box<Value>,
+        state: Option<Box<Value>>,
+    },
+}
+
+impl Value {
+    pub fn is_none(&self) -> bool {
+        matches!(self, Value::None)
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Value::Int(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Integers are converted losslessly as far as [`f64`] permits.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Int(value) => Some(*value as f64),
+            Value::Float(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::Str(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// The items of a [`Value::List`] or [`Value::Tuple`].
+    pub fn as_slice(&self) -> Option<&[Value]> {
+        match self {
+            Value::List(values) | Value::Tuple(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// Looks up the value for a string key of a [`Value::Dict`].
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Dict(entries) => entries
+                .iter()
+                .find(|(k, _)| k.as_str() == Some(key))
+                .map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn into_strings(self) -> Result<Vec<String>> {
+        match self {
+            Value::List(values) | Value::Tuple(values) => values
+                .into_iter()
+                .map(|value| match value {
+                    Value::Str(value) => Ok(value),
+                    other => Err(Error::Protocol(format!("expected a string, got {other:?}"))),
+                })
+                .collect(),
+            other => Err(Error::Protocol(format!("expected a list, got {other:?}"))),
+        }
+    }
+
+    pub(crate) fn into_f64s(self) -> Result<Vec<f64>> {
+        match self {
+            Value::Array(values) => Ok(values),
+            Value::List(values) | Value::Tuple(values) => values
+                .iter()
+                .map(|value| {
+                    value
+                        .as_f64()
+                        .ok_or_else(|| Error::Protocol(format!("expected a number, got {value:?}")))
+                })
+                .collect(),
+            other => Err(Error::Protocol(format!("expected a list, got {other:?}"))),
+        }
+    }
+
+    /// Parses the `repr()` of a python literal consisting of `None`, booleans, numbers,
+    /// strings, lists, tuples and dicts.
+    pub(crate) fn parse_python_literal(text: &str) -> Result<Value> {
+        let mut parser = LiteralParser {
+            chars: text.chars().collect(),
+            position: 0,
+        };
+        let value = parser.value()?;
+        parser.skip_whitespace();
+        if parser.position < parser.chars.len() {
+            return Err(parser.error("trailing characters"));
+        }
+        Ok(value)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Value::Bool(value)
+    }
+}
+
+impl From<i64> for Value {
+    fn from(value: i64) -> Self {
+        Value::Int(value)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(value: f64) -> Self {
+        Value::Float(value)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Value::Str(value.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::Str(value)
+    }
+}
+
+impl From<Vec<f64>> for Value {
+    fn from(values: Vec<f64>) -> Self {
+        Value::Array(values)
+    }
+}
+
+struct LiteralParser {
+    chars: Vec<char>,
+    position: usize,
+}
+
+impl LiteralParser {
+    fn error(&self, message: &str) -> Error {
+        Error::Protocol(format!(
+            "invalid python literal at {}: {message}",
+            self.position
+        ))
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek();
+        self.position += 1;
+        c
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.position += 1;
+        }
+    }
+
+    fn value(&mut self) -> Result<Value> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('[') => {
+                self.position += 1;
+                Ok(Value::List(self.sequence(']')?.0))
+            }
+            Some('(') => {
+                self.position += 1;
+                let (values, trailing_comma) = self.sequence(')')?;
+                if values.len() == 1 && !trailing_comma {
+                    Ok(values.into_iter().next().unwrap_or(Value::None))
+                } else {
+                    Ok(Value::Tuple(values))
+                }
+            }
+            Some('{') => {
+                self.position += 1;
+                self.dict()
+            }
+            Some('\'' | '"') => self.string().map(Value::Str),
+            Some('u' | 'r') if matches!(self.chars.get(self.position + 1), Some('\'' | '"')) => {
+                self.position += 1;
+                self.string().map(Value::Str)
+            }
+            Some(c) if c.is_ascii_alphabetic() => {
+                let word = self.word();
+                match word.as_str() {
+                    "None" => Ok(Value::None),
+                    "True" => Ok(Value::Bool(true)),
+                    "False" => Ok(Value::Bool(false)),
+                    "nan" => Ok(Value::Float(f64::NAN)),
+                    "inf" => Ok(Value::Float(f64::INFINITY)),
+                    _ => Err(self.error("unknown identifier")),
+                }
+            }
+            Some(c) if c.is_ascii_digit() || c == '-' || c == '+' || c == '.' => self.number(),
+            _ => Err(self.error("unexpected character")),
+        }
+    }
+
+    fn word(&mut self) -> String {
+        let start = self.position;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || c == '_')
+        {
+            self.position += 1;
+        }
+        self.chars[start..self.position].iter().collect()
+    }
+
+    fn sequence(&mut self, end: char) -> Result<(Vec<Value>, bool)> {
+        let mut values = Vec::new();
+        let mut trailing_comma = false;
+        loop {
+            self.skip_whitespace();
+            if self.peek() == Some(end) {
+                self.position += 1;
+                return Ok((values, trailing_comma));
+            }
+            values.push(self.value()?);
+            self.skip_whitespace();
+            match self.next() {
+                Some(',') => trailing_comma = true,
+                Some(c) if c == end => return Ok((values, false)),
+                _ => return Err(self.error("expected ',' or end of sequence")),
+            }
+        }
+    }
+
+    fn dict(&mut self) -> Result<Value> {
+        let mut entries = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.peek() == Some('}') {
+                self.position += 1;
+                return Ok(Value::Dict(entries));
+            }
+            let key = self.value()?;
+            self.skip_whitespace();
+            if self.next() != Some(':') {
+                return Err(self.error("expected ':'"));
+            }
+            let value = self.value()?;
+            entries.push((key, value));
+            self.skip_whitespace();
+            match self.next() {
+                Some(',') => {}
+                Some('}') => return Ok(Value::Dict(entries)),
+                _ => return Err(self.error("expected ',' or '}'")),
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String> {
+        let quote = self.next().ok_or_else(|| self.error("expected a string"))?;
+        let mut string = String::new();
+        loop {
+            match self.next() {
+                None => return Err(self.error("unterminated string")),
+                Some(c) if c == quote => return Ok(string),
+                Some('\\') => match self.next() {
+                    Some('n') => string.push('\n'),
+                    Some('r') => string.push('\r'),
+                    Some('t') => string.push('\t'),
+                    Some('0') => string.push('\0'),
+                    Some('x') => string.push(self.escaped_char(2)?),
+                    Some('u') => string.push(self.escaped_char(4)?),
+                    Some('U') => string.push(self.escaped_char(8)?),
+                    Some(c @ ('\\' | '\'' | '"')) => string.push(c),
+                    _ => return Err(self.error("unsupported escape sequence")),
+                },
+                Some(c) => string.push(c),
+            }
+        }
+    }
+
+    fn escaped_char(&mut self, digits: usize) -> Result<char> {
+        let end = self.position + digits;
+        let hex = self
+            .chars
+            .get(self.position..end)
+            .ok_or_else(|| self.error("truncated escape sequence"))?
+            .iter()
+            .collect::<String>();
+        self.position = end;
+        u32::from_str_radix(&hex, 16)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or_else(|| self.error("invalid escape sequence"))
+    }
+
+    fn number(&mut self) -> Result<Value> {
+        let start = self.position;
+        if matches!(self.peek(), Some('-' | '+')) {
+            self.position += 1;
+        }
+        if self.peek().is_some_and(|c| c.is_ascii_alphabetic()) {
+            let negative = self.chars[start] == '-';
+            return match self.word().as_str() {
+                "inf" if negative => Ok(Value::Float(f64::NEG_INFINITY)),
+                "inf" => Ok(Value::Float(f64::INFINITY)),
+                "nan" => Ok(Value::Float(f64::NAN)),
+                _ => Err(self.error("invalid number")),
+            };
+        }
+        while self
+            .peek()
+            .is_some_and(|c| c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E' | '-' | '+'))
+        {
+            self.position += 1;
+        }
+        let text = self.chars[start..self.position].iter().collect::<String>();
+        if let Ok(value) = text.parse::<i64>() {
+            Ok(Value::Int(value))
+        } else {
+            text.parse::<f64>()
+                .map(Value::Float)
+                .map_err(|_| self.error("invalid number"))
+        }
+    }
+}